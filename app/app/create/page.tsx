@@ -50,17 +50,21 @@ export default function CreateGame() {
       const gameConfigPDA = getGameConfigPDA(gameCode);
       const prizePoolPDA = getPrizePoolPDA(gameCode);
 
-      // Call initialize_game instruction
+      // Call initialize_game instruction. Without a word bank the host supplies
+      // the words, so word_category is unused; the canvas overwrites old strokes.
       const tx = await program.methods
         .initializeGame(
-          gameCode,
+          Array.from(Buffer.from(gameCode)),
           new BN(solToLamports(stake)),
-          maxPlayers
+          maxPlayers,
+          null,
+          { ring: {} }
         )
         .accounts({
           gameConfig: gameConfigPDA,
           prizePool: prizePoolPDA,
           creator: wallet.publicKey,
+          wordBank: null,
           systemProgram: SystemProgram.programId,
         })
         .rpc();
@@ -1,6 +1,6 @@
 'use client';
 
-import { useEffect, useState } from 'react';
+import { useEffect, useRef, useState } from 'react';
 import { useParams, useRouter } from 'next/navigation';
 import { useConnection, useWallet } from '@solana/wallet-adapter-react';
 import { PublicKey, SystemProgram } from '@solana/web3.js';
@@ -11,6 +11,8 @@ import {
   getPlayerStatePDA,
   lamportsToSol,
 } from '@/lib/anchor';
+import { hostWordCandidate, loadEntropy, saveHostWords } from '@/lib/secrets';
+import { pickHostWords } from '@/lib/words';
 
 type GameStatus = 'Lobby' | 'Active' | 'Ended' | 'Cancelled';
 
@@ -21,15 +23,21 @@ interface GameConfig {
   maxPlayers: number;
   playerCount: number;
   status: GameStatus;
+  wordBank: PublicKey | null;
   createdAt: number;
 }
 
 interface PlayerState {
+  address: PublicKey;
   player: PublicKey;
   score: number;
   joinedAt: number;
+  entropyRevealed: boolean;
 }
 
+// Mirrors WORD_CANDIDATES in programs/karada/src/constants.rs
+const WORD_CANDIDATES = 3;
+
 export default function GamePage() {
   const params = useParams();
   const router = useRouter();
@@ -43,6 +51,9 @@ export default function GamePage() {
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
   const [starting, setStarting] = useState(false);
+  const [roundPhase, setRoundPhase] = useState<string | null>(null);
+  const [notice, setNotice] = useState<string | null>(null);
+  const revealSent = useRef(false);
 
   // Fetch game data
   useEffect(() => {
@@ -62,9 +73,15 @@ export default function GamePage() {
           maxPlayers: config.maxPlayers,
           playerCount: config.playerCount,
           status: Object.keys(config.status)[0] as GameStatus,
+          wordBank: config.wordBank,
           createdAt: config.createdAt.toNumber(),
         });
 
+        if ('active' in config.status) {
+          const game = await program.account.game.fetch(getGamePDA(gameCode));
+          setRoundPhase(Object.keys(game.roundPhase)[0]);
+        }
+
         // Fetch all player states
         const playerAccounts = await program.account.playerState.all([
           {
@@ -76,9 +93,11 @@ export default function GamePage() {
         ]);
 
         const playerStates = playerAccounts.map((acc) => ({
+          address: acc.publicKey,
           player: acc.account.player,
           score: acc.account.score,
           joinedAt: acc.account.joinedAt.toNumber(),
+          entropyRevealed: acc.account.entropyRevealed,
         }));
 
         setPlayers(playerStates);
@@ -97,6 +116,39 @@ export default function GamePage() {
     return () => clearInterval(interval);
   }, [connection, wallet.publicKey, gameCode]);
 
+  // Every player reveals the entropy they committed to at join once the game
+  // has started; the last reveal seeds the game and draws the first words
+  useEffect(() => {
+    if (!wallet.publicKey || !gameConfig || roundPhase !== 'seeding' || revealSent.current) return;
+
+    const me = players.find((player) => player.player.equals(wallet.publicKey!));
+    if (!me || me.entropyRevealed) return;
+
+    const entropy = loadEntropy(gameCode, wallet.publicKey);
+    if (!entropy) {
+      setNotice('The entropy you joined with is not stored in this browser, so it cannot be revealed here.');
+      return;
+    }
+
+    revealSent.current = true;
+    const program = getProgram(connection, wallet);
+    program.methods
+      .revealEntropy(Array.from(entropy))
+      .accounts({
+        player: wallet.publicKey,
+        gameConfig: getGameConfigPDA(gameCode),
+        game: getGamePDA(gameCode),
+        playerState: me.address,
+        wordBank: gameConfig.wordBank,
+      })
+      .rpc()
+      .then((tx) => console.log('Entropy revealed! Transaction:', tx))
+      .catch((err) => {
+        console.error('Error revealing entropy:', err);
+        revealSent.current = false;
+      });
+  }, [connection, wallet, gameCode, gameConfig, roundPhase, players]);
+
   const handleStartGame = async () => {
     if (!wallet.publicKey || !gameConfig) return;
 
@@ -106,14 +158,28 @@ export default function GamePage() {
       const gameConfigPDA = getGameConfigPDA(gameCode);
       const gamePDA = getGamePDA(gameCode);
 
+      // Bank games draw their words once seeded, otherwise the host offers the
+      // first drawer a few words and keeps the salts to reveal them later
+      let candidates: Awaited<ReturnType<typeof hostWordCandidate>>['candidate'][] = [];
+      if (!gameConfig.wordBank) {
+        const words = await Promise.all(
+          pickHostWords(WORD_CANDIDATES).map(([word, difficulty]) => hostWordCandidate(word, difficulty))
+        );
+        candidates = words.map(({ candidate }) => candidate);
+        saveHostWords(gameCode, 0, words.map(({ secret }) => secret));
+      }
+
       const tx = await program.methods
-        .startGame()
+        .startGame(candidates)
         .accounts({
           gameConfig: gameConfigPDA,
           game: gamePDA,
           creator: wallet.publicKey,
           systemProgram: SystemProgram.programId,
         })
+        .remainingAccounts(
+          players.map((player) => ({ pubkey: player.address, isSigner: false, isWritable: false }))
+        )
         .rpc();
 
       console.log('Game started! Transaction:', tx);
@@ -262,8 +328,11 @@ export default function GamePage() {
           <div className="card mt-8 text-center">
             <h2 className="text-2xl font-bold mb-4">Game Active!</h2>
             <p className="text-gray-600">
-              Gameplay UI coming soon...
+              {roundPhase === 'seeding'
+                ? `Waiting for players to reveal their entropy (${players.filter((player) => player.entropyRevealed).length}/${players.length})...`
+                : 'Gameplay UI coming soon...'}
             </p>
+            {notice && <p className="text-sm text-red-600 mt-2">{notice}</p>}
           </div>
         )}
       </div>
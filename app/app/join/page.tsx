@@ -10,6 +10,7 @@ import {
   getPlayerStatePDA,
   getPrizePoolPDA,
 } from '@/lib/anchor';
+import { entropyCommitment, randomBytes32, saveEntropy } from '@/lib/secrets';
 
 export default function JoinGame() {
   const router = useRouter();
@@ -46,9 +47,15 @@ export default function JoinGame() {
       const gameConfig = await program.account.gameConfig.fetch(gameConfigPDA);
       console.log('Game found:', gameConfig);
 
+      // Commit to this player's share of the game seed, revealed once the game starts.
+      // Stored before sending so a join that lands can always be revealed.
+      const entropy = randomBytes32();
+      saveEntropy(code, wallet.publicKey, entropy);
+      const commitment = await entropyCommitment(wallet.publicKey, entropy);
+
       // Call join_game instruction
       const tx = await program.methods
-        .joinGame()
+        .joinGame(Array.from(commitment))
         .accounts({
           gameConfig: gameConfigPDA,
           playerState: playerStatePDA,
@@ -48,7 +48,7 @@ export function getGameConfigPDA(gameCode: string) {
  */
 export function getGamePDA(gameCode: string) {
   const [pda] = PublicKey.findProgramAddressSync(
-    [Buffer.from('game'), Buffer.from(gameCode)],
+    [Buffer.from('game'), getGameConfigPDA(gameCode).toBuffer()],
     PROGRAM_ID
   );
   return pda;
@@ -59,7 +59,7 @@ export function getGamePDA(gameCode: string) {
  */
 export function getPrizePoolPDA(gameCode: string) {
   const [pda] = PublicKey.findProgramAddressSync(
-    [Buffer.from('prize_pool'), Buffer.from(gameCode)],
+    [Buffer.from('prize_pool'), getGameConfigPDA(gameCode).toBuffer()],
     PROGRAM_ID
   );
   return pda;
@@ -70,29 +70,29 @@ export function getPrizePoolPDA(gameCode: string) {
  */
 export function getPlayerStatePDA(gameCode: string, player: PublicKey) {
   const [pda] = PublicKey.findProgramAddressSync(
-    [Buffer.from('player_state'), Buffer.from(gameCode), player.toBuffer()],
+    [Buffer.from('player_state'), getGameConfigPDA(gameCode).toBuffer(), player.toBuffer()],
     PROGRAM_ID
   );
   return pda;
 }
 
 /**
- * Derive DrawingCanvas PDA
+ * Derive DrawingCanvas PDA for a round and canvas page (always 0 for ring canvases)
  */
-export function getDrawingCanvasPDA(gameCode: string) {
+export function getDrawingCanvasPDA(gameCode: string, round: number, page = 0) {
   const [pda] = PublicKey.findProgramAddressSync(
-    [Buffer.from('drawing_canvas'), Buffer.from(gameCode)],
+    [Buffer.from('canvas'), getGamePDA(gameCode).toBuffer(), Buffer.from([round]), Buffer.from([page])],
     PROGRAM_ID
   );
   return pda;
 }
 
 /**
- * Derive GuessLog PDA
+ * Derive GuessLog PDA for a round
  */
-export function getGuessLogPDA(gameCode: string) {
+export function getGuessLogPDA(gameCode: string, round: number) {
   const [pda] = PublicKey.findProgramAddressSync(
-    [Buffer.from('guess_log'), Buffer.from(gameCode)],
+    [Buffer.from('guess_log'), getGamePDA(gameCode).toBuffer(), Buffer.from([round])],
     PROGRAM_ID
   );
   return pda;
@@ -103,7 +103,7 @@ export function getGuessLogPDA(gameCode: string) {
  */
 export function getPayoutPDA(gameCode: string, player: PublicKey) {
   const [pda] = PublicKey.findProgramAddressSync(
-    [Buffer.from('payout'), Buffer.from(gameCode), player.toBuffer()],
+    [Buffer.from('payout'), getGameConfigPDA(gameCode).toBuffer(), player.toBuffer()],
     PROGRAM_ID
   );
   return pda;
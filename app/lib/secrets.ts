@@ -0,0 +1,114 @@
+import { PublicKey } from '@solana/web3.js';
+
+// Values players commit to on-chain and reveal later. They only live in this
+// browser's localStorage: clearing it, or switching devices, before the reveal
+// means the game stalls until it can be cancelled or the round voided.
+
+// Mirrors programs/karada/src/constants.rs
+const MAX_WORD_LEN = 32;
+const MAX_GUESS_BYTES = 128;
+
+/**
+ * What the host needs to reveal a word at the end of the round
+ */
+export interface HostWordSecret {
+  word: string; // Normalized
+  salt: number[];
+}
+
+/**
+ * 32 random bytes from the browser's CSPRNG
+ */
+export function randomBytes32(): Uint8Array {
+  return crypto.getRandomValues(new Uint8Array(32));
+}
+
+async function sha256(...parts: Uint8Array[]): Promise<Uint8Array> {
+  const data = new Uint8Array(parts.reduce((len, part) => len + part.length, 0));
+  let offset = 0;
+  for (const part of parts) {
+    data.set(part, offset);
+    offset += part.length;
+  }
+  return new Uint8Array(await crypto.subtle.digest('SHA-256', data));
+}
+
+/**
+ * Same as utils::normalize_word on-chain, null where the program would reject
+ * the word with InvalidWord
+ */
+export function normalizeWord(input: string): string | null {
+  if (new TextEncoder().encode(input).length > MAX_GUESS_BYTES) return null;
+
+  const stripped = Array.from(input.normalize('NFD'))
+    .filter((c) => c < '\u0300' || c > '\u036f')
+    .map((c) => c.toLowerCase())
+    .join('')
+    .replace(/[^\p{Alphabetic}\p{N}\p{M}\p{White_Space}-]/gu, '');
+  const normalized = stripped
+    .split(/\p{White_Space}+/u)
+    .filter((part) => part.length > 0)
+    .join(' ')
+    .normalize('NFC');
+
+  const len = Array.from(normalized).length;
+  return len > 0 && len <= MAX_WORD_LEN ? normalized : null;
+}
+
+/**
+ * utils::entropy_commitment: sha256("commit" || player || entropy)
+ */
+export async function entropyCommitment(player: PublicKey, entropy: Uint8Array) {
+  return sha256(new TextEncoder().encode('commit'), player.toBytes(), entropy);
+}
+
+const entropyKey = (gameCode: string, player: PublicKey) =>
+  `karada:entropy:${gameCode}:${player.toBase58()}`;
+
+export function saveEntropy(gameCode: string, player: PublicKey, entropy: Uint8Array) {
+  localStorage.setItem(entropyKey(gameCode, player), JSON.stringify(Array.from(entropy)));
+}
+
+export function loadEntropy(gameCode: string, player: PublicKey): Uint8Array | null {
+  const stored = localStorage.getItem(entropyKey(gameCode, player));
+  return stored ? new Uint8Array(JSON.parse(stored)) : null;
+}
+
+/**
+ * Builds a WordCandidate for start_game or end_round. The salt stays in this
+ * browser until the host reveals the word at the end of the round.
+ */
+export async function hostWordCandidate(word: string, difficulty: number) {
+  const normalized = normalizeWord(word);
+  if (!normalized) throw new Error(`Invalid word: ${word}`);
+
+  const salt = randomBytes32();
+  const commitment = await sha256(salt, new TextEncoder().encode(normalized));
+  const shape = Array.from(normalized)
+    .map((c) => (c === ' ' || c === '-' ? c : '_'))
+    .join('');
+
+  const candidate = {
+    word: {
+      commitment: Array.from(commitment),
+      shape,
+      lettersRoot: new Array(32).fill(0), // Letter hints off
+      alternates: [],
+    },
+    difficulty,
+    bankIndex: null,
+  };
+  const secret: HostWordSecret = { word: normalized, salt: Array.from(salt) };
+  return { candidate, secret };
+}
+
+const hostWordsKey = (gameCode: string, round: number) => `karada:words:${gameCode}:${round}`;
+
+export function saveHostWords(gameCode: string, round: number, secrets: HostWordSecret[]) {
+  localStorage.setItem(hostWordsKey(gameCode, round), JSON.stringify(secrets));
+}
+
+export function loadHostWords(gameCode: string, round: number): HostWordSecret[] {
+  const stored = localStorage.getItem(hostWordsKey(gameCode, round));
+  return stored ? JSON.parse(stored) : [];
+}
@@ -0,0 +1,21 @@
+// Words the host offers when the game has no word bank, as [word, difficulty]
+const HOST_WORDS: [string, number][] = [
+  ['cat', 1], ['house', 1], ['tree', 1], ['sun', 1], ['fish', 1], ['apple', 1],
+  ['car', 1], ['ball', 1], ['moon', 1], ['flower', 1], ['boat', 1], ['star', 1],
+  ['guitar', 2], ['rainbow', 2], ['castle', 2], ['penguin', 2], ['volcano', 2],
+  ['bicycle', 2], ['ice cream', 2], ['lighthouse', 2], ['snowman', 2], ['t-rex', 2],
+  ['astronaut', 3], ['waterfall', 3], ['skyscraper', 3], ['hot air balloon', 3],
+  ['submarine', 3], ['telescope', 3], ['traffic jam', 3], ['haunted house', 3],
+];
+
+/**
+ * `count` distinct words at random
+ */
+export function pickHostWords(count: number): [string, number][] {
+  const words = [...HOST_WORDS];
+  for (let i = words.length - 1; i > 0; i--) {
+    const j = Math.floor(Math.random() * (i + 1));
+    [words[i], words[j]] = [words[j], words[i]];
+  }
+  return words.slice(0, count);
+}
@@ -22,6 +22,12 @@ pub const PRIZE_POOL_SEED: &[u8] = b"prize_pool";
 #[constant]
 pub const PAYOUT_SEED: &[u8] = b"payout";
 
+#[constant]
+pub const GAME_RESULT_SEED: &[u8] = b"game_result";
+
+#[constant]
+pub const WORD_BANK_SEED: &[u8] = b"word_bank";
+
 // Game Constants
 #[constant]
 pub const MIN_PLAYERS: u8 = 2;
@@ -32,12 +38,95 @@ pub const MAX_PLAYERS: u8 = 10;
 #[constant]
 pub const ROUND_DURATION: i64 = 80; // seconds
 
+#[constant]
+pub const SEEDING_DURATION: i64 = 60; // seconds for every player to reveal their entropy after start
+
+#[constant]
+pub const REVEAL_GRACE: i64 = 60; // seconds after the round timer for the host to reveal the word
+
 #[constant]
 pub const LOBBY_TIMEOUT: i64 = 600; // 10 minutes
 
 #[constant]
 pub const CLAIM_DEADLINE: i64 = 604800; // 7 days
 
+// Strokes
+#[constant]
+pub const CANVAS_WIDTH: u16 = 800; // Default canvas size in drawing coordinates
+
+#[constant]
+pub const CANVAS_HEIGHT: u16 = 600;
+
+#[constant]
+pub const MIN_STROKE_WIDTH: u8 = 1;
+
+#[constant]
+pub const MAX_STROKE_WIDTH: u8 = 20;
+
+pub const MAX_STROKE_POINTS: usize = 25; // (x, y) pairs per stroke
+
+// add_stroke_encoded: a 1232 byte transaction with two signatures, eight account
+// keys and the other arguments leaves 783 bytes for the encoded points
+pub const MAX_ENCODED_STROKE_BYTES: usize = 780;
+
+pub const MAX_ENCODED_STROKE_POINTS: usize = 384; // (x, y) pairs, a smooth curve takes about 2 bytes per point
+
+pub const MAX_BATCH_POINTS: usize = 100; // (x, y) pairs across one add_strokes call
+
+// Canvas ring buffer or page, sized to fill the 10 KiB limit for accounts created by the program
+pub const CANVAS_STROKE_SLOTS: usize = 128;
+
+pub const CANVAS_POINT_SLOTS: usize = 3552; // u16 coordinates
+
+#[constant]
+pub const MAX_CANVAS_PAGES: u8 = 4; // Per round in paged games
+
+// Word banks
+pub const MAX_WORD_BANK_ENTRIES: usize = 64; // Fits the Game.used_words bitmask
+
+#[constant]
+pub const MAX_WORD_DIFFICULTY: u8 = 3;
+
+// Word choice
+pub const WORD_CANDIDATES: usize = 3;
+
+#[constant]
+pub const CHOOSE_DURATION: i64 = 15; // seconds before a word is picked for the drawer
+
+pub const MAX_WORD_LEN: usize = 32; // characters, after normalization
+
+pub const MAX_WORD_ALTERNATES: usize = 2; // keeps a full WordBank under the 10 KiB creation limit
+
+pub const MAX_WORD_BYTES: usize = 4 * MAX_WORD_LEN; // UTF-8 bytes of a normalized word
+
+pub const MAX_GUESSES_PER_ROUND: usize = 5; // per player, guesses are only scored when the round ends
+
+pub const MAX_LOGGED_GUESSES: usize = MAX_PLAYERS as usize * MAX_GUESSES_PER_ROUND;
+
+pub const MAX_GUESS_BYTES: usize = 128; // raw input accepted by submit_guess
+
+// Letter hints, as fractions of round_duration (basis points) after which the next letter may be revealed
+#[constant]
+pub const HINT_SCHEDULE_BPS: [u16; 2] = [
+    5000, // first letter at half time
+    7500, // second letter with a quarter left
+];
+
+// "Close guess" Bloom filter over salted near-miss spellings of the word. A
+// MAX_WORD_LEN word has ~1,700 spellings within edit distance 1, which this
+// size and hash count keep at about a 1% false positive rate.
+pub const CLOSE_FILTER_BYTES: usize = 2048;
+
+pub const CLOSE_FILTER_HASHES: usize = 7;
+
+// Points multiplier per difficulty (basis points, 10000 = 1x)
+#[constant]
+pub const DIFFICULTY_POINTS_BPS: [u16; 3] = [
+    10000, // easy
+    12500, // medium
+    15000, // hard
+];
+
 // Payout multipliers (basis points, 10000 = 100%)
 // These sum to 9700 (97%), 3% goes to protocol fee
 #[constant]
@@ -41,6 +41,9 @@ pub enum ErrorCode {
     #[msg("Lobby timeout exceeded")]
     LobbyTimeout,
 
+    #[msg("Lobby has not timed out yet")]
+    LobbyNotExpired,
+
     #[msg("Claim deadline exceeded")]
     ClaimDeadlineExceeded,
 
@@ -53,12 +56,99 @@ pub enum ErrorCode {
     #[msg("Round is over")]
     RoundOver,
 
+    #[msg("Round is still in progress")]
+    RoundInProgress,
+
+    #[msg("Revealed word does not match the commitment")]
+    RevealMismatch,
+
+    #[msg("Host can still reveal the word")]
+    RevealWindowOpen,
+
     #[msg("Cannot guess as drawer")]
     CannotGuessAsDrawer,
 
     #[msg("Invalid word")]
     InvalidWord,
 
+    #[msg("No guesses left this round")]
+    NoGuessesLeft,
+
+    #[msg("Only the word bank authority can perform this action")]
+    NotWordBankAuthority,
+
+    #[msg("Word bank is full")]
+    WordBankFull,
+
+    #[msg("Word bank has no words left for this game")]
+    WordBankExhausted,
+
+    #[msg("Word bank does not match the game")]
+    InvalidWordBank,
+
+    #[msg("Revealed entropy does not match the commitment")]
+    EntropyMismatch,
+
+    #[msg("Players can still reveal their entropy")]
+    SeedingNotExpired,
+
+    #[msg("Drawer has not chosen a word yet")]
+    WordNotChosen,
+
+    #[msg("Word has already been chosen this round")]
+    WordAlreadyChosen,
+
+    #[msg("No hint is due yet")]
+    HintNotDue,
+
+    #[msg("Hint does not match the committed word")]
+    InvalidHint,
+
+    #[msg("Invalid close guess filter")]
+    InvalidCloseFilter,
+
+    #[msg("Stroke points must be a non-empty list of (x, y) pairs")]
+    InvalidStrokePoints,
+
+    #[msg("Stroke has too many points")]
+    TooManyStrokePoints,
+
+    #[msg("Stroke point lies outside the canvas")]
+    StrokeOutOfBounds,
+
+    #[msg("Stroke width out of range")]
+    InvalidStrokeWidth,
+
+    #[msg("Stroke color must be 0xRRGGBB")]
+    InvalidStrokeColor,
+
+    #[msg("Encoded stroke could not be decoded")]
+    InvalidStrokeEncoding,
+
+    #[msg("Stroke batch is empty or too large")]
+    InvalidStrokeBatch,
+
     #[msg("Arithmetic overflow")]
     ArithmeticOverflow,
+
+    #[msg("Player state account does not belong to this game")]
+    InvalidPlayerState,
+
+    #[msg("Player listed more than once")]
+    DuplicatePlayer,
+
+    #[msg("Player is not in the final standings")]
+    PlayerNotRanked,
+
+    #[msg("Payouts would exceed the prize pool")]
+    PayoutExceedsPool,
+
+    #[msg("Prize pool does not hold enough lamports")]
+    InsufficientPoolFunds,
+
+    #[msg("Canvas is full")]
+    CanvasFull,
+
+    #[msg("Next canvas page required")]
+    CanvasPageMissing,
 }
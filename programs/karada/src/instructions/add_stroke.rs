@@ -1,7 +1,8 @@
 #[allow(unexpected_cfgs, unused_imports)]
 use anchor_lang::prelude::*;
-use crate::{Game, DrawingCanvas, DrawStroke, GameStatus, GameConfig, ErrorCode};
+use crate::{Game, DrawingCanvas, CanvasMode, GameStatus, GameConfig, RoundPhase, ErrorCode};
 use crate::constants::*;
+use crate::stroke_codec::decode_points;
 
 #[derive(Accounts)]
 pub struct AddStroke<'info> {
@@ -23,68 +24,184 @@ pub struct AddStroke<'info> {
     #[account(
         init_if_needed,
         payer = drawer,
-        space = 8 + 32 + 1 + 4 + (100 * (2 * 50 + 4 + 1 + 8)) + 1, // Reasonable space for strokes
-        seeds = [CANVAS_SEED, game.key().as_ref(), &[game.current_round]],
+        space = DrawingCanvas::SPACE,
+        seeds = [CANVAS_SEED, game.key().as_ref(), &[game.current_round], &[game.canvas_page]],
         bump,
     )]
-    pub canvas: Account<'info, DrawingCanvas>,
+    pub canvas: AccountLoader<'info, DrawingCanvas>,
+
+    // Paged games only: the page after `canvas`, needed once `canvas` is full
+    #[account(
+        init_if_needed,
+        payer = drawer,
+        space = DrawingCanvas::SPACE,
+        seeds = [CANVAS_SEED, game.key().as_ref(), &[game.current_round], &[game.canvas_page.wrapping_add(1)]],
+        bump,
+    )]
+    pub next_canvas: Option<AccountLoader<'info, DrawingCanvas>>,
 
     pub system_program: Program<'info, System>,
 }
 
+// One stroke of a batch, see add_strokes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct StrokeInput {
+    pub points: Vec<u16>,
+    pub color: u32,
+    pub width: u8,
+}
+
 pub fn handler(
     ctx: Context<AddStroke>,
     points: Vec<u16>,
     color: u32,
     width: u8,
 ) -> Result<()> {
-    let game = &ctx.accounts.game;
-    let canvas = &mut ctx.accounts.canvas;
+    add_single_stroke(ctx, StrokeInput { points, color, width }, MAX_STROKE_POINTS)
+}
+
+// Same as handler with points in the stroke_codec encoding, which fits longer
+// strokes in a transaction
+pub fn encoded_handler(
+    ctx: Context<AddStroke>,
+    encoded_points: Vec<u8>,
+    color: u32,
+    width: u8,
+) -> Result<()> {
+    require!(
+        encoded_points.len() <= MAX_ENCODED_STROKE_BYTES,
+        crate::error::ErrorCode::TooManyStrokePoints
+    );
+    let points = decode_points(&encoded_points, MAX_ENCODED_STROKE_POINTS)
+        .ok_or(crate::error::ErrorCode::InvalidStrokeEncoding)?;
+
+    add_single_stroke(ctx, StrokeInput { points, color, width }, MAX_ENCODED_STROKE_POINTS)
+}
+
+fn add_single_stroke(mut ctx: Context<AddStroke>, stroke: StrokeInput, max_points: usize) -> Result<()> {
     let clock = Clock::get()?;
 
+    append_strokes(&mut ctx, std::slice::from_ref(&stroke), max_points, clock.unix_timestamp)?;
+
+    // Emit event for real-time sync
+    emit!(StrokeAdded {
+        game: ctx.accounts.game.key(),
+        round: ctx.accounts.game.current_round,
+        drawer: ctx.accounts.drawer.key(),
+        page: ctx.accounts.game.canvas_page,
+        points: stroke.points,
+        color: stroke.color,
+        width: stroke.width,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Appends several strokes in order, all or none
+pub fn batch_handler(mut ctx: Context<AddStroke>, strokes: Vec<StrokeInput>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let total_points: usize = strokes.iter().map(|stroke| stroke.points.len() / 2).sum();
+    require!(
+        !strokes.is_empty() && total_points <= MAX_BATCH_POINTS,
+        crate::error::ErrorCode::InvalidStrokeBatch
+    );
+
+    let first_stroke = append_strokes(&mut ctx, &strokes, MAX_STROKE_POINTS, clock.unix_timestamp)?;
+
+    emit!(StrokesAdded {
+        game: ctx.accounts.game.key(),
+        round: ctx.accounts.game.current_round,
+        drawer: ctx.accounts.drawer.key(),
+        page: ctx.accounts.game.canvas_page,
+        first_stroke,
+        strokes,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Checks the drawer and every stroke (of up to max_points points), then writes them to the canvas. In paged
+// games a batch that doesn't fit the current page starts the next one.
+// Returns the index of the first stroke written within its page.
+fn append_strokes(
+    ctx: &mut Context<AddStroke>,
+    strokes: &[StrokeInput],
+    max_points: usize,
+    now: i64,
+) -> Result<u64> {
+    let accounts = &mut ctx.accounts;
+    let game = &mut accounts.game;
+
     // Check if drawer is current drawer
     let current_drawer = game.players.get(game.current_drawer_index as usize)
         .ok_or(crate::error::ErrorCode::NotCurrentDrawer)?;
 
     require!(
-        *current_drawer == ctx.accounts.drawer.key(),
+        *current_drawer == accounts.drawer.key(),
         crate::error::ErrorCode::NotCurrentDrawer
     );
 
     // Check if round is still active
     require!(
-        game.time_remaining > 0,
+        game.round_phase == RoundPhase::Drawing,
+        crate::error::ErrorCode::WordNotChosen
+    );
+    require!(
+        game.time_remaining_at(now) > 0,
         crate::error::ErrorCode::RoundOver
     );
 
-    // Initialize canvas if needed
-    if canvas.game == Pubkey::default() {
-        canvas.game = game.key();
-        canvas.round = game.current_round;
-        canvas.bump = ctx.bumps.canvas;
+    for stroke in strokes.iter() {
+        game.validate_stroke(&stroke.points, max_points, stroke.color, stroke.width)?;
     }
 
-    // Add stroke
-    let stroke = DrawStroke {
-        points,
-        color,
-        width,
-        timestamp: clock.unix_timestamp,
-    };
+    let mut canvas = load_canvas(&accounts.canvas, game, ctx.bumps.canvas)?;
 
-    canvas.strokes.push(stroke);
+    let coordinates: usize = strokes.iter().map(|stroke| stroke.points.len()).sum();
+    if accounts.game_config.canvas_mode == CanvasMode::Paged && !canvas.has_room(strokes.len(), coordinates) {
+        require!(
+            game.canvas_page + 1 < MAX_CANVAS_PAGES,
+            crate::error::ErrorCode::CanvasFull
+        );
+        let next_canvas = accounts.next_canvas.as_ref()
+            .ok_or(crate::error::ErrorCode::CanvasPageMissing)?;
+        let next_bump = ctx.bumps.next_canvas
+            .ok_or(crate::error::ErrorCode::CanvasPageMissing)?;
 
-    // Emit event for real-time sync
-    emit!(StrokeAdded {
-        game: game.key(),
-        round: game.current_round,
-        drawer: ctx.accounts.drawer.key(),
-        color,
-        width,
-        timestamp: clock.unix_timestamp,
-    });
+        drop(canvas);
+        game.canvas_page += 1;
+        canvas = load_canvas(next_canvas, game, next_bump)?;
+    }
 
-    Ok(())
+    // Add strokes
+    let first_stroke = canvas.stroke_count;
+    for stroke in strokes.iter() {
+        canvas.push_stroke(&stroke.points, stroke.color, stroke.width, now);
+    }
+
+    Ok(first_stroke)
+}
+
+// Loads a canvas page, initializing it on first use
+fn load_canvas<'a>(
+    loader: &'a AccountLoader<DrawingCanvas>,
+    game: &Account<Game>,
+    bump: u8,
+) -> Result<std::cell::RefMut<'a, DrawingCanvas>> {
+    match loader.load_mut() {
+        Ok(canvas) => Ok(canvas),
+        Err(_) => {
+            let mut canvas = loader.load_init()?;
+            canvas.game = game.key();
+            canvas.round = game.current_round;
+            canvas.page = game.canvas_page;
+            canvas.bump = bump;
+            Ok(canvas)
+        }
+    }
 }
 
 #[event]
@@ -92,7 +209,20 @@ pub struct StrokeAdded {
     pub game: Pubkey,
     pub round: u8,
     pub drawer: Pubkey,
+    pub page: u8,                   // Canvas page the stroke was written to
+    pub points: Vec<u16>,           // Kept here too since the canvas overwrites old strokes
     pub color: u32,
     pub width: u8,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct StrokesAdded {
+    pub game: Pubkey,
+    pub round: u8,
+    pub drawer: Pubkey,
+    pub page: u8,                   // Canvas page the batch was written to
+    pub first_stroke: u64,          // Index of the first stroke of the batch within the page
+    pub strokes: Vec<StrokeInput>,  // Kept here too since the canvas overwrites old strokes
+    pub timestamp: i64,
+}
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{transfer, Transfer};
-use crate::{GameConfig, PrizePool, PlayerState, GameStatus, ErrorCode};
+use crate::{GameConfig, Game, PrizePool, PlayerState, GameStatus, RoundPhase, ErrorCode};
 use crate::constants::*;
+use crate::utils::{load_player_state, withdraw_from_pool};
 
 #[derive(Accounts)]
 pub struct CancelGame<'info> {
@@ -43,16 +43,111 @@ pub fn cancel_handler(ctx: Context<CancelGame>) -> Result<()> {
     Ok(())
 }
 
+#[derive(Accounts)]
+pub struct ExpireLobby<'info> {
+    // Can be called by anyone once the lobby has timed out
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = game_config.status == GameStatus::Lobby @ crate::error::ErrorCode::GameAlreadyStarted,
+    )]
+    pub game_config: Account<'info, GameConfig>,
+}
+
+pub fn expire_handler(ctx: Context<ExpireLobby>) -> Result<()> {
+    let game_config = &mut ctx.accounts.game_config;
+    let clock = Clock::get()?;
+
+    require!(
+        game_config.is_lobby_expired(clock.unix_timestamp),
+        crate::error::ErrorCode::LobbyNotExpired
+    );
+
+    // Update status
+    game_config.status = GameStatus::Cancelled;
+    game_config.ended_at = clock.unix_timestamp;
+
+    msg!("Lobby expired. Players must claim refunds individually.");
+
+    emit!(GameCancelled {
+        game: game_config.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExpireSeeding<'info> {
+    // Can be called by anyone once the seeding deadline has passed
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = game_config.status == GameStatus::Active @ crate::error::ErrorCode::GameNotActive,
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    /// CHECK: Receives the game account rent
+    #[account(
+        mut,
+        address = game_config.creator @ crate::error::ErrorCode::NotCreator,
+    )]
+    pub creator: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [GAME_SEED, game_config.key().as_ref()],
+        bump = game.bump,
+        constraint = game.round_phase == RoundPhase::Seeding @ crate::error::ErrorCode::GameAlreadyStarted,
+        close = creator,
+    )]
+    pub game: Account<'info, Game>,
+}
+
+// A player who never reveals their entropy can only stop the game, stakes are refunded
+pub fn expire_seeding_handler(ctx: Context<ExpireSeeding>) -> Result<()> {
+    let game_config = &mut ctx.accounts.game_config;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp >= ctx.accounts.game.seeding_deadline,
+        crate::error::ErrorCode::SeedingNotExpired
+    );
+
+    // Update status
+    game_config.status = GameStatus::Cancelled;
+    game_config.ended_at = clock.unix_timestamp;
+
+    msg!("Entropy not revealed in time. Players must claim refunds individually.");
+
+    emit!(GameCancelled {
+        game: game_config.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct RefundStake<'info> {
     #[account(mut)]
     pub player: Signer<'info>,
 
     #[account(
-        constraint = game_config.status == GameStatus::Cancelled @ crate::error::ErrorCode::GameNotActive,
+        mut,
+        constraint = matches!(game_config.status, GameStatus::Cancelled | GameStatus::Lobby) @ crate::error::ErrorCode::GameNotActive,
     )]
     pub game_config: Account<'info, GameConfig>,
 
+    /// CHECK: Receives the pool and config rent once the last stake is refunded
+    #[account(
+        mut,
+        address = game_config.creator @ crate::error::ErrorCode::NotCreator,
+    )]
+    pub creator: AccountInfo<'info>,
+
     #[account(
         mut,
         seeds = [PLAYER_STATE_SEED, game_config.key().as_ref(), player.key().as_ref()],
@@ -67,39 +162,148 @@ pub struct RefundStake<'info> {
         bump = prize_pool.bump,
     )]
     pub prize_pool: Account<'info, PrizePool>,
-
-    pub system_program: Program<'info, System>,
 }
 
 pub fn refund_handler(ctx: Context<RefundStake>) -> Result<()> {
     let prize_pool = &mut ctx.accounts.prize_pool;
-    let game_config = &ctx.accounts.game_config;
+    let game_config = &mut ctx.accounts.game_config;
     let player = &ctx.accounts.player;
+    let clock = Clock::get()?;
+
+    cancel_if_expired(game_config, clock.unix_timestamp)?;
+    refund_player(game_config, prize_pool, &player.to_account_info(), clock.unix_timestamp)?;
+    close_if_refunded(game_config, prize_pool, &ctx.accounts.creator)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RefundAll<'info> {
+    // Can be called by anyone (typically backend cron)
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = matches!(game_config.status, GameStatus::Cancelled | GameStatus::Lobby) @ crate::error::ErrorCode::GameNotActive,
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    /// CHECK: Receives the pool and config rent once the last stake is refunded
+    #[account(
+        mut,
+        address = game_config.creator @ crate::error::ErrorCode::NotCreator,
+    )]
+    pub creator: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [PRIZE_POOL_SEED, game_config.key().as_ref()],
+        bump = prize_pool.bump,
+    )]
+    pub prize_pool: Account<'info, PrizePool>,
+}
+
+// Remaining accounts: (PlayerState, player wallet) pairs, both writable.
+// Can be called repeatedly with different batches until every stake is back.
+pub fn refund_all_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RefundAll<'info>>,
+) -> Result<()> {
+    let prize_pool = &mut ctx.accounts.prize_pool;
+    let game_config = &mut ctx.accounts.game_config;
+    let clock = Clock::get()?;
+
+    require!(
+        ctx.remaining_accounts.chunks_exact(2).remainder().is_empty(),
+        crate::error::ErrorCode::InvalidPlayerCount
+    );
+
+    cancel_if_expired(game_config, clock.unix_timestamp)?;
+
+    for pair in ctx.remaining_accounts.chunks_exact(2) {
+        let player_state = load_player_state(&pair[0], &game_config.key())?;
+        let player = &pair[1];
+        require_keys_eq!(
+            player_state.player,
+            player.key(),
+            crate::error::ErrorCode::InvalidPlayerState
+        );
+
+        refund_player(game_config, prize_pool, player, clock.unix_timestamp)?;
+
+        // Rent goes back to the player, not the cranker
+        player_state.close(player.clone())?;
+    }
+
+    close_if_refunded(game_config, prize_pool, &ctx.accounts.creator)?;
+
+    Ok(())
+}
+
+// Refunding from an abandoned lobby cancels it on the way
+fn cancel_if_expired(game_config: &mut Account<GameConfig>, now: i64) -> Result<()> {
+    if game_config.status == GameStatus::Lobby {
+        require!(
+            game_config.is_lobby_expired(now),
+            crate::error::ErrorCode::LobbyNotExpired
+        );
+
+        game_config.status = GameStatus::Cancelled;
+        game_config.ended_at = now;
+
+        emit!(GameCancelled {
+            game: game_config.key(),
+            timestamp: now,
+        });
+    }
+
+    Ok(())
+}
 
+fn refund_player<'info>(
+    game_config: &mut Account<'info, GameConfig>,
+    prize_pool: &mut Account<'info, PrizePool>,
+    player: &AccountInfo<'info>,
+    now: i64,
+) -> Result<()> {
     // Transfer stake back to player
-    let game_config_key = game_config.key();
-    let seeds = &[
-        PRIZE_POOL_SEED,
-        game_config_key.as_ref(),
-        &[prize_pool.bump],
-    ];
-    let signer_seeds = &[&seeds[..]];
-
-    transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: prize_pool.to_account_info(),
-                to: player.to_account_info(),
-            },
-            signer_seeds,
-        ),
-        game_config.stake_amount,
-    )?;
+    withdraw_from_pool(prize_pool, player, game_config.stake_amount)?;
+
+    prize_pool.total_distributed = prize_pool.total_distributed
+        .checked_add(game_config.stake_amount)
+        .ok_or(crate::error::ErrorCode::ArithmeticOverflow)?;
+
+    game_config.player_count = game_config.player_count
+        .checked_sub(1)
+        .ok_or(crate::error::ErrorCode::ArithmeticOverflow)?;
 
     msg!("Stake refunded: {} lamports to {}",
          game_config.stake_amount, player.key());
 
+    emit!(StakeRefunded {
+        game: game_config.key(),
+        player: player.key(),
+        amount: game_config.stake_amount,
+        remaining_players: game_config.player_count,
+        outstanding: prize_pool.total_staked.saturating_sub(prize_pool.total_distributed),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+// Last refund closes the game and returns its rent to the creator
+fn close_if_refunded<'info>(
+    game_config: &mut Account<'info, GameConfig>,
+    prize_pool: &mut Account<'info, PrizePool>,
+    creator: &AccountInfo<'info>,
+) -> Result<()> {
+    if game_config.player_count == 0 {
+        prize_pool.close(creator.clone())?;
+        game_config.close(creator.clone())?;
+
+        msg!("All stakes refunded. Game closed.");
+    }
+
     Ok(())
 }
 
@@ -108,3 +312,13 @@ pub struct GameCancelled {
     pub game: Pubkey,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct StakeRefunded {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub amount: u64,
+    pub remaining_players: u8,
+    pub outstanding: u64, // Staked lamports not yet paid back
+    pub timestamp: i64,
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::{Game, GameConfig, GameStatus, RoundPhase};
+use crate::constants::*;
+use crate::utils::random_index;
+
+#[derive(Accounts)]
+pub struct ChooseWord<'info> {
+    // The drawer, or anyone once the choosing timeout has passed
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = game_config.status == GameStatus::Active @ crate::error::ErrorCode::GameNotActive,
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [GAME_SEED, game_config.key().as_ref()],
+        bump = game.bump,
+        constraint = game.round_phase == RoundPhase::Choosing @ crate::error::ErrorCode::WordAlreadyChosen,
+    )]
+    pub game: Account<'info, Game>,
+}
+
+pub fn handler(ctx: Context<ChooseWord>, index: u8) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let clock = Clock::get()?;
+
+    let drawer = *game.players.get(game.current_drawer_index as usize)
+        .ok_or(crate::error::ErrorCode::NotCurrentDrawer)?;
+
+    // Idle drawers get a random candidate picked for them
+    let auto_picked = clock.unix_timestamp >= game.choosing_deadline;
+    let index = if auto_picked {
+        let pick_seed = hashv(&[&game.round_seed, b"choose"]).to_bytes();
+        random_index(&pick_seed, game.candidates.len())
+    } else {
+        require!(
+            ctx.accounts.authority.key() == drawer,
+            crate::error::ErrorCode::NotCurrentDrawer
+        );
+        index as usize
+    };
+
+    game.choose_word(index, clock.unix_timestamp)?;
+
+    emit!(WordChosen {
+        game: game.key(),
+        round: game.current_round,
+        drawer,
+        difficulty: game.word_difficulty,
+        auto_picked,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Round {} word chosen (difficulty {})", game.current_round, game.word_difficulty);
+
+    Ok(())
+}
+
+#[event]
+pub struct WordChosen {
+    pub game: Pubkey,
+    pub round: u8,
+    pub drawer: Pubkey,
+    pub difficulty: u8,
+    pub auto_picked: bool,
+    pub timestamp: i64,
+}
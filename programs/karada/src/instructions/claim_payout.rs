@@ -1,10 +1,9 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{transfer, Transfer};
-use crate::{GameConfig, PrizePool, Payout, GameStatus, ErrorCode};
+use crate::{GameConfig, GameResult, PrizePool, Payout, GameStatus, ErrorCode};
 use crate::constants::*;
+use crate::utils::withdraw_from_pool;
 
 #[derive(Accounts)]
-#[instruction(rank: u8)]
 pub struct CreatePayout<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -14,7 +13,14 @@ pub struct CreatePayout<'info> {
     )]
     pub game_config: Account<'info, GameConfig>,
 
-    /// CHECK: Player who will claim this payout
+    #[account(
+        mut,
+        seeds = [GAME_RESULT_SEED, game_config.key().as_ref()],
+        bump = game_result.bump,
+    )]
+    pub game_result: Account<'info, GameResult>,
+
+    /// CHECK: Player who will claim this payout, must appear in game_result
     pub player: AccountInfo<'info>,
 
     #[account(
@@ -35,19 +41,33 @@ pub struct CreatePayout<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn create_payout_handler(ctx: Context<CreatePayout>, rank: u8) -> Result<()> {
+pub fn create_payout_handler(ctx: Context<CreatePayout>) -> Result<()> {
     let payout = &mut ctx.accounts.payout;
+    let game_result = &mut ctx.accounts.game_result;
     let prize_pool = &ctx.accounts.prize_pool;
     let game_config = &ctx.accounts.game_config;
     let clock = Clock::get()?;
 
-    // Calculate payout amount based on rank
-    let multiplier = *PAYOUT_MULTIPLIERS.get(rank as usize).unwrap_or(&0) as u64;
-    let payout_amount = (prize_pool.total_staked * multiplier) / 10000;
+    // Amount comes from the rank recorded at finalization
+    let player = ctx.accounts.player.key();
+    let rank = game_result.standings.iter()
+        .position(|standing| standing.player == player)
+        .ok_or(crate::error::ErrorCode::PlayerNotRanked)?;
+    let payout_amount = game_result.standings[rank].amount;
+
+    // Never allocate more than was staked
+    game_result.total_allocated = game_result.total_allocated
+        .checked_add(payout_amount)
+        .ok_or(crate::error::ErrorCode::ArithmeticOverflow)?;
+    require!(
+        game_result.total_allocated <= prize_pool.total_staked,
+        crate::error::ErrorCode::PayoutExceedsPool
+    );
 
     // Initialize payout
     payout.game_config = game_config.key();
-    payout.player = ctx.accounts.player.key();
+    payout.player = player;
+    payout.rank = rank as u8;
     payout.amount = payout_amount;
     payout.claimed = false;
     payout.created_at = clock.unix_timestamp;
@@ -55,7 +75,7 @@ pub fn create_payout_handler(ctx: Context<CreatePayout>, rank: u8) -> Result<()>
     payout.bump = ctx.bumps.payout;
 
     msg!("Payout created for player {} (rank {}): {} lamports",
-         ctx.accounts.player.key(), rank, payout_amount);
+         player, rank, payout_amount);
 
     Ok(())
 }
@@ -84,8 +104,6 @@ pub struct ClaimPayout<'info> {
         bump = prize_pool.bump,
     )]
     pub prize_pool: Account<'info, PrizePool>,
-
-    pub system_program: Program<'info, System>,
 }
 
 pub fn claim_payout_handler(ctx: Context<ClaimPayout>) -> Result<()> {
@@ -101,25 +119,7 @@ pub fn claim_payout_handler(ctx: Context<ClaimPayout>) -> Result<()> {
     );
 
     // Transfer payout from prize pool to player
-    let game_config_key = game_config.key();
-    let seeds = &[
-        PRIZE_POOL_SEED,
-        game_config_key.as_ref(),
-        &[prize_pool.bump],
-    ];
-    let signer_seeds = &[&seeds[..]];
-
-    transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: prize_pool.to_account_info(),
-                to: ctx.accounts.player.to_account_info(),
-            },
-            signer_seeds,
-        ),
-        payout.amount,
-    )?;
+    withdraw_from_pool(prize_pool, &ctx.accounts.player.to_account_info(), payout.amount)?;
 
     // Update payout record
     payout.claimed = true;
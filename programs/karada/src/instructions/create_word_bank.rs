@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::{WordBank, WordEntry, is_valid_shape};
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(name: [u8; 32])]
+pub struct CreateWordBank<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + WordBank::INIT_SPACE,
+        seeds = [WORD_BANK_SEED, authority.key().as_ref(), &name],
+        bump,
+    )]
+    pub word_bank: Account<'info, WordBank>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_handler(ctx: Context<CreateWordBank>, name: [u8; 32]) -> Result<()> {
+    let word_bank = &mut ctx.accounts.word_bank;
+
+    word_bank.authority = ctx.accounts.authority.key();
+    word_bank.name = name;
+    word_bank.entries = vec![];
+    word_bank.retired = 0;
+    word_bank.bump = ctx.bumps.word_bank;
+
+    msg!("Word bank created by {}", word_bank.authority);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddWords<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [WORD_BANK_SEED, word_bank.authority.as_ref(), &word_bank.name],
+        bump = word_bank.bump,
+        has_one = authority @ crate::error::ErrorCode::NotWordBankAuthority,
+    )]
+    pub word_bank: Account<'info, WordBank>,
+}
+
+pub fn add_words_handler(ctx: Context<AddWords>, entries: Vec<WordEntry>) -> Result<()> {
+    let word_bank = &mut ctx.accounts.word_bank;
+
+    require!(
+        word_bank.entries.len() + entries.len() <= MAX_WORD_BANK_ENTRIES,
+        crate::error::ErrorCode::WordBankFull
+    );
+
+    for entry in entries.iter() {
+        require!(
+            (1..=MAX_WORD_DIFFICULTY).contains(&entry.difficulty),
+            crate::error::ErrorCode::InvalidWord
+        );
+        require!(is_valid_shape(&entry.shape), crate::error::ErrorCode::InvalidWord);
+        require!(
+            entry.alternates.len() <= MAX_WORD_ALTERNATES,
+            crate::error::ErrorCode::InvalidWord
+        );
+    }
+
+    word_bank.entries.extend(entries);
+
+    msg!("Word bank now holds {} words", word_bank.entries.len());
+
+    Ok(())
+}
@@ -1,9 +1,13 @@
 use anchor_lang::prelude::*;
-use crate::{Game, GameConfig, GameStatus, ErrorCode};
+use crate::{Game, GameConfig, GuessLog, PlayerState, WordBank, GameStatus, RoundPhase, WordCandidate, ErrorCode};
 use crate::constants::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::utils::{load_roster, next_candidates, normalize_word};
 
 #[derive(Accounts)]
 pub struct EndRound<'info> {
+    // The creator reveals host words; anyone can end bank rounds, or host rounds
+    // once the reveal deadline has passed
     pub authority: Signer<'info>,
 
     #[account(
@@ -15,25 +19,144 @@ pub struct EndRound<'info> {
         mut,
         seeds = [GAME_SEED, game_config.key().as_ref()],
         bump = game.bump,
+        constraint = !game.completed @ crate::error::ErrorCode::GameNotActive,
+        constraint = game.round_phase == RoundPhase::Drawing @ crate::error::ErrorCode::WordNotChosen,
     )]
     pub game: Account<'info, Game>,
+
+    // Only exists if someone guessed this round
+    #[account(
+        mut,
+        seeds = [GUESS_LOG_SEED, game.key().as_ref(), &[game.current_round]],
+        bump = guess_log.bump,
+    )]
+    pub guess_log: Option<Account<'info, GuessLog>>,
+
+    // Required when the game was created with a word bank, holds the salt of bank words
+    pub word_bank: Option<Account<'info, WordBank>>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct EndRoundParams {
-    pub next_word: [u8; 32], // Word for next round (or empty if game ending)
+    pub revealed_word: Option<String>,       // Plaintext of the round's word. None voids host words after the reveal deadline, optional for bank words
+    pub salt: [u8; 32],                      // Salt behind the word's commitment, ignored for bank words
+    pub letters_secret: [u8; 32],            // Secret behind the letter hint nonces
+    pub next_words: Vec<WordCandidate>,      // Candidates for next round (empty if game ending, drawn from a word bank or not the creator)
 }
 
-pub fn handler(ctx: Context<EndRound>, params: EndRoundParams) -> Result<()> {
+// Remaining accounts: the writable PlayerState of every roster member, in roster order
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, EndRound<'info>>,
+    params: EndRoundParams,
+) -> Result<()> {
     let game = &mut ctx.accounts.game;
     let game_config = &ctx.accounts.game_config;
     let clock = Clock::get()?;
 
+    let mut roster = load_roster(ctx.remaining_accounts, &game_config.key(), &game.players)?;
+
+    // Guesses are only scored here, so the round always runs its full time
+    require!(
+        game.time_remaining_at(clock.unix_timestamp) == 0,
+        crate::error::ErrorCode::RoundInProgress
+    );
+
+    // Only the creator can reveal host words or supply new ones, and a reveal
+    // that doesn't match is rejected rather than voiding the round. Bank words
+    // weren't picked by the creator, who usually doesn't know them, so anyone
+    // who does may reveal them and the round counts either way.
+    let is_creator = ctx.accounts.authority.key() == game_config.creator;
+    require!(
+        is_creator || params.next_words.is_empty(),
+        crate::error::ErrorCode::NotCreator
+    );
+    require!(
+        is_creator || game.word_bank_index.is_some() || params.revealed_word.is_none(),
+        crate::error::ErrorCode::NotCreator
+    );
+
+    // Bank salts are public, a host word's salt is only known once it is revealed
+    let bank_salt = match game.word_bank_index {
+        Some(index) => {
+            let word_bank = ctx.accounts.word_bank.as_ref()
+                .ok_or(crate::error::ErrorCode::InvalidWordBank)?;
+            require_keys_eq!(
+                word_bank.key(),
+                game_config.word_bank.ok_or(crate::error::ErrorCode::InvalidWordBank)?,
+                crate::error::ErrorCode::InvalidWordBank
+            );
+            let entry = word_bank.entries.get(index as usize)
+                .ok_or(crate::error::ErrorCode::InvalidWordBank)?;
+            Some(entry.salt)
+        }
+        None => None,
+    };
+    let salt = bank_salt.unwrap_or(params.salt);
+
+    let revealed_word = match params.revealed_word.as_deref() {
+        Some(word) => {
+            let word = normalize_word(word)?;
+            require!(
+                game.verify_reveal(&word, &salt, &params.letters_secret),
+                crate::error::ErrorCode::RevealMismatch
+            );
+            Some(word)
+        }
+        None if bank_salt.is_some() => None,
+        None => {
+            // Host never revealed, so the round's guesses can't be checked
+            require!(
+                clock.unix_timestamp >= game.reveal_deadline(),
+                crate::error::ErrorCode::RevealWindowOpen
+            );
+            None
+        }
+    };
+
+    if let Some(word) = revealed_word.as_ref() {
+        emit!(WordRevealed {
+            game: game.key(),
+            round: game.current_round,
+            word: word.clone(),
+            salt,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    if revealed_word.is_some() || bank_salt.is_some() {
+        if let Some(guess_log) = ctx.accounts.guess_log.as_mut() {
+            score_guesses(game, guess_log, &mut roster, &salt, clock.unix_timestamp)?;
+
+            // Round is over, the word can now appear alongside the guesses
+            guess_log.revealed_word = revealed_word;
+        }
+    } else {
+        emit!(RoundVoided {
+            game: game.key(),
+            round: game.current_round,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Word was not revealed in time. Round {} voided.", game.current_round);
+    }
+
+    // Clear per-round state in the same instruction as the round bump
+    for player_state in roster.iter_mut() {
+        player_state.has_guessed_current_round = false;
+        player_state.exit(&crate::ID)?;
+    }
+
     // Move to next drawer
     let next_drawer_index = (game.current_drawer_index + 1) as usize;
 
+    // Without the creator nobody can supply host words, so a host game ends
+    // after a missed reveal instead of stalling
+    let host_missing = !is_creator && game_config.word_bank.is_none();
+
     // Check if all players have had their turn
-    if next_drawer_index >= game.players.len() {
+    if next_drawer_index >= game.players.len() || host_missing {
+        game.completed = true;
+
         // Game should end - emit event
         emit!(GameReadyToEnd {
             game: game.key(),
@@ -48,7 +171,13 @@ pub fn handler(ctx: Context<EndRound>, params: EndRoundParams) -> Result<()> {
     // Reset for next round
     game.current_drawer_index = next_drawer_index as u8;
     game.current_round += 1;
-    game.current_word = params.next_word;
+
+    // Derived from the seed alone so the caller can't pick a favourable slot.
+    // This makes bank candidates for every round known once the game is seeded.
+    game.round_seed = hashv(&[&game.seed, &[game.current_round]]).to_bytes();
+
+    let candidates = next_candidates(game, game_config, ctx.accounts.word_bank.as_ref(), params.next_words, &game.round_seed)?;
+    game.begin_choosing(candidates, clock.unix_timestamp)?;
     game.round_start_time = clock.unix_timestamp;
     game.time_remaining = game.round_duration;
 
@@ -66,6 +195,47 @@ pub fn handler(ctx: Context<EndRound>, params: EndRoundParams) -> Result<()> {
     Ok(())
 }
 
+// Checks the logged guesses in the order they were made. Each player scores
+// for their first correct guess, with points decaying from the round start.
+fn score_guesses(
+    game: &Account<Game>,
+    guess_log: &mut GuessLog,
+    roster: &mut [Account<PlayerState>],
+    salt: &[u8; 32],
+    now: i64,
+) -> Result<()> {
+    for guess in guess_log.guesses.iter_mut() {
+        if !game.accepts(salt, &guess.word) {
+            continue;
+        }
+        let Some(player_state) = roster.iter_mut().find(|player_state| player_state.player == guess.player) else {
+            continue;
+        };
+        if player_state.has_guessed_current_round {
+            continue;
+        }
+
+        let points = game.guess_points(guess.timestamp);
+        player_state.score = player_state.score
+            .checked_add(points)
+            .ok_or(crate::error::ErrorCode::ArithmeticOverflow)?;
+        player_state.has_guessed_current_round = true;
+        guess.correct = true;
+        guess.points_awarded = points;
+
+        emit!(GuessScored {
+            game: game.key(),
+            round: game.current_round,
+            player: guess.player,
+            points,
+            guessed_at: guess.timestamp,
+            timestamp: now,
+        });
+    }
+
+    Ok(())
+}
+
 #[event]
 pub struct RoundStarted {
     pub game: Pubkey,
@@ -81,3 +251,29 @@ pub struct GameReadyToEnd {
     pub total_rounds: u8,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct WordRevealed {
+    pub game: Pubkey,
+    pub round: u8,
+    pub word: String,
+    pub salt: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoundVoided {
+    pub game: Pubkey,
+    pub round: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GuessScored {
+    pub game: Pubkey,
+    pub round: u8,
+    pub player: Pubkey,
+    pub points: u32,
+    pub guessed_at: i64,
+    pub timestamp: i64,
+}
@@ -1,13 +1,13 @@
 use anchor_lang::prelude::*;
-use crate::{GameConfig, Game, PrizePool, Payout, GameStatus, ErrorCode};
+use crate::{GameConfig, Game, GameResult, Standing, PrizePool, GameStatus};
 use crate::constants::*;
+use crate::utils::load_roster;
 
 // This instruction should be called after undelegating from ER
 #[derive(Accounts)]
-#[instruction(player_scores: Vec<PlayerScore>)]
 pub struct FinalizeGame<'info> {
     #[account(mut)]
-    pub authority: Signer<'info>, // Backend authority
+    pub authority: Signer<'info>, // Anyone, standings come from PlayerState accounts
 
     #[account(
         mut,
@@ -18,9 +18,19 @@ pub struct FinalizeGame<'info> {
     #[account(
         seeds = [GAME_SEED, game_config.key().as_ref()],
         bump = game.bump,
+        constraint = game.completed @ crate::error::ErrorCode::GameNotEnded,
     )]
     pub game: Account<'info, Game>,
 
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + GameResult::INIT_SPACE,
+        seeds = [GAME_RESULT_SEED, game_config.key().as_ref()],
+        bump,
+    )]
+    pub game_result: Account<'info, GameResult>,
+
     #[account(
         seeds = [PRIZE_POOL_SEED, game_config.key().as_ref()],
         bump = prize_pool.bump,
@@ -30,29 +40,54 @@ pub struct FinalizeGame<'info> {
     pub system_program: Program<'info, System>,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct PlayerScore {
-    pub player: Pubkey,
-    pub score: u32,
-}
-
+// Remaining accounts: the PlayerState of every roster member, in roster order
 // Note: Payout PDAs are created in separate transactions due to account limits
-pub fn handler(ctx: Context<FinalizeGame>, player_scores: Vec<PlayerScore>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizeGame<'info>>) -> Result<()> {
     let game_config = &mut ctx.accounts.game_config;
+    let game = &ctx.accounts.game;
+    let game_result = &mut ctx.accounts.game_result;
     let prize_pool = &ctx.accounts.prize_pool;
     let clock = Clock::get()?;
 
-    // Sort players by score (descending)
-    let mut ranked_players = player_scores.clone();
-    ranked_players.sort_by(|a, b| b.score.cmp(&a.score));
+    let roster = load_roster(ctx.remaining_accounts, &game_config.key(), &game.players)?;
+
+    // Sort players by score (descending), ties keep roster order
+    let mut ranked_players: Vec<Standing> = roster.iter()
+        .map(|player_state| Standing {
+            player: player_state.player,
+            score: player_state.score,
+            amount: 0,
+        })
+        .collect();
+    ranked_players.sort_by_key(|p| std::cmp::Reverse(p.score));
 
     // Calculate payouts based on rankings
     let total_pool = prize_pool.total_staked;
     let player_count = ranked_players.len();
 
+    let mut total_payout: u64 = 0;
+    for (rank, standing) in ranked_players.iter_mut().enumerate() {
+        let multiplier = *PAYOUT_MULTIPLIERS.get(rank).unwrap_or(&0) as u128;
+        standing.amount = (total_pool as u128 * multiplier / 10000) as u64;
+        total_payout = total_payout
+            .checked_add(standing.amount)
+            .ok_or(crate::error::ErrorCode::ArithmeticOverflow)?;
+    }
+    require!(
+        total_payout <= total_pool,
+        crate::error::ErrorCode::PayoutExceedsPool
+    );
+
     msg!("Finalizing game with {} players", player_count);
     msg!("Total prize pool: {} lamports", total_pool);
 
+    // Persist standings
+    game_result.game_config = game_config.key();
+    game_result.standings = ranked_players.clone();
+    game_result.total_allocated = 0;
+    game_result.finalized_at = clock.unix_timestamp;
+    game_result.bump = ctx.bumps.game_result;
+
     // Update game config
     game_config.status = GameStatus::Ended;
     game_config.ended_at = clock.unix_timestamp;
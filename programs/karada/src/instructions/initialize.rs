@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::{GameConfig, PrizePool, GameStatus, ErrorCode};
+use crate::{GameConfig, PrizePool, WordBank, GameStatus, CanvasMode, ErrorCode};
 use crate::constants::*;
 
 #[derive(Accounts)]
@@ -26,6 +26,9 @@ pub struct InitializeGame<'info> {
     )]
     pub prize_pool: Account<'info, PrizePool>,
 
+    // Optional public word list (answers are not hidden), otherwise the creator supplies words each round
+    pub word_bank: Option<Account<'info, WordBank>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -34,6 +37,8 @@ pub fn handler(
     game_code: [u8; 6],
     stake_amount: u64,
     max_players: u8,
+    word_category: Option<u8>,
+    canvas_mode: CanvasMode,
 ) -> Result<()> {
     // Validate inputs
     require!(
@@ -41,6 +46,13 @@ pub fn handler(
         crate::error::ErrorCode::InvalidPlayerCount
     );
 
+    if let Some(word_bank) = ctx.accounts.word_bank.as_ref() {
+        require!(
+            word_bank.eligible_count(word_category, word_bank.retired) >= max_players as usize,
+            crate::error::ErrorCode::WordBankExhausted
+        );
+    }
+
     let game_config = &mut ctx.accounts.game_config;
     let prize_pool = &mut ctx.accounts.prize_pool;
     let clock = Clock::get()?;
@@ -53,6 +65,9 @@ pub fn handler(
     game_config.player_count = 0;
     game_config.status = GameStatus::Lobby;
     game_config.prize_pool = prize_pool.key();
+    game_config.word_bank = ctx.accounts.word_bank.as_ref().map(|word_bank| word_bank.key());
+    game_config.word_category = word_category;
+    game_config.canvas_mode = canvas_mode;
     game_config.created_at = clock.unix_timestamp;
     game_config.started_at = 0;
     game_config.ended_at = 0;
@@ -34,7 +34,8 @@ pub struct JoinGame<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<JoinGame>) -> Result<()> {
+// entropy_commitment: utils::entropy_commitment of a secret the player reveals after start
+pub fn handler(ctx: Context<JoinGame>, entropy_commitment: [u8; 32]) -> Result<()> {
     let game_config = &mut ctx.accounts.game_config;
     let player_state = &mut ctx.accounts.player_state;
     let prize_pool = &mut ctx.accounts.prize_pool;
@@ -70,6 +71,8 @@ pub fn handler(ctx: Context<JoinGame>) -> Result<()> {
     player_state.has_guessed_current_round = false;
     player_state.is_active = true;
     player_state.joined_at = clock.unix_timestamp;
+    player_state.entropy_commitment = entropy_commitment;
+    player_state.entropy_revealed = false;
     player_state.bump = ctx.bumps.player_state;
 
     // Update game config
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use crate::{GameConfig, PlayerState, PrizePool, GameStatus};
+use crate::constants::*;
+use crate::utils::withdraw_from_pool;
+
+#[derive(Accounts)]
+pub struct LeaveGame<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = game_config.status == GameStatus::Lobby @ crate::error::ErrorCode::GameAlreadyStarted,
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [PLAYER_STATE_SEED, game_config.key().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        close = player, // Close account and return rent to player
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    #[account(
+        mut,
+        seeds = [PRIZE_POOL_SEED, game_config.key().as_ref()],
+        bump = prize_pool.bump,
+    )]
+    pub prize_pool: Account<'info, PrizePool>,
+}
+
+pub fn handler(ctx: Context<LeaveGame>) -> Result<()> {
+    let game_config = &mut ctx.accounts.game_config;
+    let prize_pool = &mut ctx.accounts.prize_pool;
+    let player = &ctx.accounts.player;
+    let clock = Clock::get()?;
+
+    // Return stake to player
+    withdraw_from_pool(prize_pool, &player.to_account_info(), game_config.stake_amount)?;
+
+    // The stake never entered play, so it comes off the pool total
+    prize_pool.total_staked = prize_pool.total_staked
+        .checked_sub(game_config.stake_amount)
+        .ok_or(crate::error::ErrorCode::ArithmeticOverflow)?;
+
+    // Free the seat
+    game_config.player_count = game_config.player_count
+        .checked_sub(1)
+        .ok_or(crate::error::ErrorCode::ArithmeticOverflow)?;
+
+    msg!("Player {} left game", player.key());
+    msg!("Total players: {}/{}", game_config.player_count, game_config.max_players);
+
+    emit!(PlayerLeft {
+        game: game_config.key(),
+        player: player.key(),
+        amount: game_config.stake_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PlayerLeft {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
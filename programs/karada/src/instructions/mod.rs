@@ -1,21 +1,33 @@
 pub mod initialize;
 pub mod join_game;
+pub mod leave_game;
 pub mod start_game;
+pub mod reveal_entropy;
+pub mod choose_word;
 pub mod add_stroke;
 pub mod submit_guess;
+pub mod reveal_hint;
+pub mod set_close_filter;
 pub mod tick;
 pub mod end_round;
 pub mod finalize_game;
 pub mod claim_payout;
 pub mod cancel_game;
+pub mod create_word_bank;
 
 pub use initialize::*;
 pub use join_game::*;
+pub use leave_game::*;
 pub use start_game::*;
+pub use reveal_entropy::*;
+pub use choose_word::*;
 pub use add_stroke::*;
 pub use submit_guess::*;
+pub use reveal_hint::*;
+pub use set_close_filter::*;
 pub use tick::*;
 pub use end_round::*;
 pub use finalize_game::*;
 pub use claim_payout::*;
 pub use cancel_game::*;
+pub use create_word_bank::*;
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::{Game, GameConfig, PlayerState, WordBank, GameStatus, RoundPhase};
+use crate::constants::*;
+use crate::utils::{entropy_commitment, mix_entropy, next_candidates, random_index};
+
+#[derive(Accounts)]
+pub struct RevealEntropy<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        constraint = game_config.status == GameStatus::Active @ crate::error::ErrorCode::GameNotActive,
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [GAME_SEED, game_config.key().as_ref()],
+        bump = game.bump,
+        constraint = game.round_phase == RoundPhase::Seeding @ crate::error::ErrorCode::GameAlreadyStarted,
+    )]
+    pub game: Account<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [PLAYER_STATE_SEED, game_config.key().as_ref(), player.key().as_ref()],
+        bump = player_state.bump,
+        constraint = !player_state.entropy_revealed @ crate::error::ErrorCode::EntropyMismatch,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+
+    // Required when the game was created with a word bank, used by the last reveal
+    #[account(mut)]
+    pub word_bank: Option<Account<'info, WordBank>>,
+}
+
+// Every contribution was fixed at join, before anyone revealed, so nobody can
+// steer the seed. The last player to reveal can only withhold, which lets the
+// game be cancelled via expire_seeding rather than choosing another outcome.
+pub fn handler(ctx: Context<RevealEntropy>, entropy: [u8; 32]) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let game_config = &ctx.accounts.game_config;
+    let player_state = &mut ctx.accounts.player_state;
+    let player = ctx.accounts.player.key();
+    let clock = Clock::get()?;
+
+    require!(
+        entropy_commitment(&player, &entropy) == player_state.entropy_commitment,
+        crate::error::ErrorCode::EntropyMismatch
+    );
+
+    mix_entropy(&mut game.seed, &player, &entropy);
+    player_state.entropy_revealed = true;
+    game.entropy_reveals += 1;
+
+    msg!("Entropy revealed: {}/{}", game.entropy_reveals, game.players.len());
+
+    if (game.entropy_reveals as usize) < game.players.len() {
+        return Ok(());
+    }
+
+    // Everyone revealed: fix the seed, then shuffle and draw the first words
+    let game_config_key = game_config.key();
+    game.seed = hashv(&[&game.seed, game_config_key.as_ref()]).to_bytes();
+    let seed = game.seed;
+
+    // Shuffle drawer order (Fisher-Yates)
+    for i in (1..game.players.len()).rev() {
+        let j = random_index(&hashv(&[&seed, &[i as u8]]).to_bytes(), i + 1);
+        game.players.swap(i, j);
+    }
+
+    // Bank entries for every round are retired up front so no other game draws them
+    if let Some(word_bank) = ctx.accounts.word_bank.as_mut() {
+        require_keys_eq!(
+            word_bank.key(),
+            game_config.word_bank.ok_or(crate::error::ErrorCode::InvalidWordBank)?,
+            crate::error::ErrorCode::InvalidWordBank
+        );
+        let reserve_seed = hashv(&[&seed, b"reserve"]).to_bytes();
+        game.reserved_words = word_bank.reserve(
+            game_config.word_category,
+            game.players.len() * WORD_CANDIDATES,
+            &reserve_seed,
+        );
+        require!(
+            game.reserved_words.count_ones() as usize >= game.players.len(),
+            crate::error::ErrorCode::WordBankExhausted
+        );
+    }
+
+    game.round_seed = hashv(&[&seed, &[game.current_round]]).to_bytes();
+    let host_candidates = std::mem::take(&mut game.candidates);
+    let candidates = next_candidates(game, game_config, ctx.accounts.word_bank.as_ref(), host_candidates, &game.round_seed)?;
+    game.begin_choosing(candidates, clock.unix_timestamp)?;
+
+    emit!(GameSeeded {
+        game: game.key(),
+        seed,
+        drawer: game.players[0],
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Game seeded. Round {} starting.", game.current_round);
+
+    Ok(())
+}
+
+#[event]
+pub struct GameSeeded {
+    pub game: Pubkey,
+    pub seed: [u8; 32],
+    pub drawer: Pubkey,
+    pub timestamp: i64,
+}
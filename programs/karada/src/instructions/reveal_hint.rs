@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use crate::{Game, GameConfig, GameStatus, RoundPhase};
+use crate::constants::*;
+use crate::utils::{letter_leaf, verify_letter_proof};
+
+#[derive(Accounts)]
+pub struct RevealHint<'info> {
+    // Can be called by anyone holding a valid proof (typically the drawer or backend)
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = game_config.status == GameStatus::Active @ crate::error::ErrorCode::GameNotActive,
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [GAME_SEED, game_config.key().as_ref()],
+        bump = game.bump,
+        constraint = game.round_phase == RoundPhase::Drawing @ crate::error::ErrorCode::WordNotChosen,
+    )]
+    pub game: Account<'info, Game>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RevealHintParams {
+    pub position: u8,               // Character index in the word
+    pub letter: String,             // Single character of the normalized word
+    pub nonce: [u8; 32],            // letter_nonce(letters_secret, position)
+    pub proof: Vec<[u8; 32]>,       // Merkle siblings from leaf to root
+}
+
+pub fn handler(ctx: Context<RevealHint>, params: RevealHintParams) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let clock = Clock::get()?;
+
+    // Hints unlock at fixed fractions of the round
+    let fraction = *HINT_SCHEDULE_BPS.get(game.hints_revealed as usize)
+        .ok_or(crate::error::ErrorCode::HintNotDue)? as i64;
+    let elapsed = clock.unix_timestamp - game.round_start_time;
+    require!(
+        elapsed * 10000 >= game.round_duration * fraction,
+        crate::error::ErrorCode::HintNotDue
+    );
+    require!(
+        game.time_remaining_at(clock.unix_timestamp) > 0,
+        crate::error::ErrorCode::RoundOver
+    );
+    require!(
+        game.letters_root != [0u8; 32],
+        crate::error::ErrorCode::InvalidHint
+    );
+
+    // Only hidden letters can be revealed, one character at a time
+    let mut hint: Vec<char> = game.word_hint.chars().collect();
+    let position = params.position as usize;
+    require!(
+        hint.get(position) == Some(&'_'),
+        crate::error::ErrorCode::InvalidHint
+    );
+    let mut letters = params.letter.chars();
+    let letter = match (letters.next(), letters.next()) {
+        (Some(letter), None) if letter != '_' && letter != ' ' && letter != '-' => letter,
+        _ => return err!(crate::error::ErrorCode::InvalidHint),
+    };
+
+    let leaf = letter_leaf(&params.nonce, params.position, &params.letter);
+    require!(
+        verify_letter_proof(&game.letters_root, leaf, &params.proof),
+        crate::error::ErrorCode::InvalidHint
+    );
+
+    hint[position] = letter;
+    game.word_hint = hint.into_iter().collect();
+    game.hints_revealed += 1;
+
+    emit!(HintRevealed {
+        game: game.key(),
+        round: game.current_round,
+        position: params.position,
+        letter: params.letter,
+        hint: game.word_hint.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct HintRevealed {
+    pub game: Pubkey,
+    pub round: u8,
+    pub position: u8,
+    pub letter: String,
+    pub hint: String,
+    pub timestamp: i64,
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::{Game, GameConfig, GameStatus, RoundPhase};
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetCloseFilter<'info> {
+    // The creator hosts the words, so only they can describe near misses
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = game_config.creator == authority.key() @ crate::error::ErrorCode::NotCreator,
+        constraint = game_config.status == GameStatus::Active @ crate::error::ErrorCode::GameNotActive,
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [GAME_SEED, game_config.key().as_ref()],
+        bump = game.bump,
+        constraint = game.round_phase == RoundPhase::Drawing @ crate::error::ErrorCode::WordNotChosen,
+    )]
+    pub game: Account<'info, Game>,
+}
+
+// Posted separately from the word itself, in chunks, because the filter doesn't
+// fit in one transaction. Chunks are OR-ed in, so bits can only be added.
+// The filter is keyed by the public round seed so guesses can be flagged as they
+// come in, which also lets anyone test a word list against it offline: a word
+// whose edit distance 1 variants all hit is almost certainly the answer. Posting
+// a filter trades the word's secrecy for close-guess feedback.
+pub fn handler(ctx: Context<SetCloseFilter>, offset: u16, chunk: Vec<u8>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+
+    let start = offset as usize;
+    require!(
+        !chunk.is_empty() && start + chunk.len() <= CLOSE_FILTER_BYTES,
+        crate::error::ErrorCode::InvalidCloseFilter
+    );
+
+    if game.close_filter.is_empty() {
+        game.close_filter = vec![0u8; CLOSE_FILTER_BYTES];
+    }
+    for (byte, bits) in game.close_filter[start..start + chunk.len()].iter_mut().zip(chunk.iter()) {
+        *byte |= bits;
+    }
+
+    msg!("Close guess filter bytes {}..{} set for round {}", start, start + chunk.len(), game.current_round);
+
+    Ok(())
+}
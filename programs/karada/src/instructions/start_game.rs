@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use crate::{GameConfig, Game, GameStatus, ErrorCode};
+use crate::{GameConfig, Game, GameStatus, RoundPhase, WordCandidate, ErrorCode};
 use crate::constants::*;
+use crate::utils::load_player_state;
 
 #[derive(Accounts)]
 pub struct StartGame<'info> {
@@ -28,27 +29,74 @@ pub struct StartGame<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<StartGame>) -> Result<()> {
+// Remaining accounts: one PlayerState PDA per joined player, in any order
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, StartGame<'info>>,
+    candidates: Vec<WordCandidate>,
+) -> Result<()> {
     let game_config = &mut ctx.accounts.game_config;
     let game = &mut ctx.accounts.game;
     let clock = Clock::get()?;
 
+    // Every joined player must be supplied exactly once
+    require!(
+        ctx.remaining_accounts.len() == game_config.player_count as usize,
+        crate::error::ErrorCode::InvalidPlayerCount
+    );
+
+    let mut roster: Vec<(i64, Pubkey)> = Vec::with_capacity(ctx.remaining_accounts.len());
+    for info in ctx.remaining_accounts.iter() {
+        let player_state = load_player_state(info, &game_config.key())?;
+        require!(
+            !roster.iter().any(|(_, player)| *player == player_state.player),
+            crate::error::ErrorCode::DuplicatePlayer
+        );
+        roster.push((player_state.joined_at, player_state.player));
+    }
+
+    // Join order until reveal_entropy shuffles it
+    roster.sort();
+
+    // Host words are kept until the seed is known, bank words are drawn then.
+    // Checked here so a bad list fails now rather than at the last reveal.
+    require!(
+        candidates.len() <= WORD_CANDIDATES,
+        crate::error::ErrorCode::InvalidWord
+    );
+    if game_config.word_bank.is_some() {
+        require!(candidates.is_empty(), crate::error::ErrorCode::InvalidWord);
+    } else {
+        require!(
+            !candidates.is_empty() && candidates.iter().all(|candidate| candidate.bank_index.is_none()),
+            crate::error::ErrorCode::InvalidWord
+        );
+    }
+
     // Update game config status
     game_config.status = GameStatus::Active;
     game_config.started_at = clock.unix_timestamp;
 
     // Initialize active game state
     game.config = game_config.key();
-    game.current_word = [0u8; 32]; // Will be set in first round
     game.current_drawer_index = 0;
     game.current_round = 0;
     game.round_start_time = clock.unix_timestamp;
     game.round_duration = ROUND_DURATION;
+    game.canvas_width = CANVAS_WIDTH;
+    game.canvas_height = CANVAS_HEIGHT;
     game.time_remaining = ROUND_DURATION;
-    game.players = vec![]; // Will be populated from PlayerState accounts
+    game.players = roster.iter().map(|(_, player)| *player).collect();
+    game.completed = false;
+    game.used_words = 0;
+    game.reserved_words = 0;
+    game.seed = [0u8; 32];
+    game.seeding_deadline = clock.unix_timestamp + SEEDING_DURATION;
+    game.entropy_reveals = 0;
+    game.round_phase = RoundPhase::Seeding;
+    game.candidates = candidates;
     game.bump = ctx.bumps.game;
 
-    msg!("Game started!");
+    msg!("Game started! Waiting for players to reveal their entropy.");
     msg!("Total players: {}", game_config.player_count);
     msg!("Game PDA will be delegated to Ephemeral Rollup");
 
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use crate::{Game, PlayerState, GuessLog, Guess, GameConfig, GameStatus, ErrorCode};
+use crate::{Game, PlayerState, GuessLog, Guess, GameConfig, GameStatus, RoundPhase, ErrorCode};
 use crate::constants::*;
+use crate::utils::{close_filter_contains, normalize_word};
 
 #[derive(Accounts)]
 pub struct SubmitGuess<'info> {
@@ -20,18 +21,16 @@ pub struct SubmitGuess<'info> {
     pub game: Account<'info, Game>,
 
     #[account(
-        mut,
         seeds = [PLAYER_STATE_SEED, game_config.key().as_ref(), player.key().as_ref()],
         bump = player_state.bump,
         constraint = player_state.is_active @ crate::error::ErrorCode::GameCancelled,
-        constraint = !player_state.has_guessed_current_round @ crate::error::ErrorCode::AlreadyGuessed,
     )]
     pub player_state: Account<'info, PlayerState>,
 
     #[account(
         init_if_needed,
         payer = player,
-        space = 8 + 32 + 1 + 4 + (100 * (32 + 32 + 8 + 1 + 4)) + 1, // Space for guesses
+        space = GuessLog::SPACE,
         seeds = [GUESS_LOG_SEED, game.key().as_ref(), &[game.current_round]],
         bump,
     )]
@@ -42,13 +41,17 @@ pub struct SubmitGuess<'info> {
 
 pub fn handler(ctx: Context<SubmitGuess>, word: String) -> Result<()> {
     let game = &ctx.accounts.game;
-    let player_state = &mut ctx.accounts.player_state;
     let guess_log = &mut ctx.accounts.guess_log;
+    let player = ctx.accounts.player.key();
     let clock = Clock::get()?;
 
     // Check if round is still active
     require!(
-        game.time_remaining > 0,
+        game.round_phase == RoundPhase::Drawing,
+        crate::error::ErrorCode::WordNotChosen
+    );
+    require!(
+        game.time_remaining_at(clock.unix_timestamp) > 0,
         crate::error::ErrorCode::RoundOver
     );
 
@@ -56,10 +59,21 @@ pub fn handler(ctx: Context<SubmitGuess>, word: String) -> Result<()> {
     let current_drawer = game.players.get(game.current_drawer_index as usize)
         .ok_or(crate::error::ErrorCode::NotCurrentDrawer)?;
     require!(
-        *current_drawer != ctx.accounts.player.key(),
+        *current_drawer != player,
         crate::error::ErrorCode::CannotGuessAsDrawer
     );
 
+    // Guesses are only scored by end_round, once the salt is public, so nobody
+    // can tell from the chain which guess is right. They are still readable, so
+    // the per-player cap keeps others from simply resubmitting all of them.
+    require!(
+        guess_log.guesses_by(&player) < MAX_GUESSES_PER_ROUND,
+        crate::error::ErrorCode::NoGuessesLeft
+    );
+
+    let word_normalized = normalize_word(&word)?;
+    let is_close = close_filter_contains(&game.close_filter, &game.round_seed, &word_normalized);
+
     // Initialize guess log if needed
     if guess_log.game == Pubkey::default() {
         guess_log.game = game.key();
@@ -67,51 +81,22 @@ pub fn handler(ctx: Context<SubmitGuess>, word: String) -> Result<()> {
         guess_log.bump = ctx.bumps.guess_log;
     }
 
-    // Check if guess is correct (compare with current_word)
-    // Note: In production, current_word should be hashed/encrypted
-    let word_lower = word.to_lowercase();
-    let current_word_str = String::from_utf8_lossy(&game.current_word);
-    let is_correct = word_lower == current_word_str.trim_end_matches('\0').to_lowercase();
-
-    let points_awarded = if is_correct {
-        // Calculate time-decay points
-        let elapsed = clock.unix_timestamp - game.round_start_time;
-        let time_factor = game.round_duration - elapsed.min(game.round_duration);
-        let points_range = MAX_POINTS - MIN_POINTS;
-
-        let points = MIN_POINTS + ((points_range as i64 * time_factor) / game.round_duration) as u32;
-
-        // Award points
-        player_state.score = player_state.score
-            .checked_add(points)
-            .ok_or(crate::error::ErrorCode::ArithmeticOverflow)?;
-
-        player_state.has_guessed_current_round = true;
-
-        points
-    } else {
-        0
-    };
-
     // Log the guess
-    let guess = Guess {
-        player: ctx.accounts.player.key(),
-        word: word.clone(),
+    guess_log.guesses.push(Guess {
+        player,
+        word: word_normalized.clone(),
         timestamp: clock.unix_timestamp,
-        correct: is_correct,
-        points_awarded,
-    };
-
-    guess_log.guesses.push(guess);
+        correct: false,
+        points_awarded: 0,
+    });
 
     // Emit event
     emit!(GuessSubmitted {
         game: game.key(),
         round: game.current_round,
-        player: ctx.accounts.player.key(),
-        word,
-        correct: is_correct,
-        points: points_awarded,
+        player,
+        word: word_normalized,
+        close: is_close,
         timestamp: clock.unix_timestamp,
     });
 
@@ -123,8 +108,7 @@ pub struct GuessSubmitted {
     pub game: Pubkey,
     pub round: u8,
     pub player: Pubkey,
-    pub word: String,
-    pub correct: bool,
-    pub points: u32,
+    pub word: String,               // Normalized guess, scored when the round ends
+    pub close: bool,                // Within edit distance 1 of the word (about 1% false positives for the longest words)
     pub timestamp: i64,
 }
@@ -24,11 +24,9 @@ pub fn handler(ctx: Context<Tick>) -> Result<()> {
     let game = &mut ctx.accounts.game;
     let clock = Clock::get()?;
 
-    // Calculate time elapsed since round start
-    let elapsed = clock.unix_timestamp - game.round_start_time;
-
-    // Update time remaining
-    game.time_remaining = (game.round_duration - elapsed).max(0);
+    // Optional crank: gameplay instructions derive time from the clock themselves,
+    // this only refreshes the cached value and notifies the frontend
+    game.time_remaining = game.time_remaining_at(clock.unix_timestamp);
 
     // Emit event for frontend
     emit!(TimerTick {
@@ -2,6 +2,8 @@ pub mod constants;
 pub mod error;
 pub mod instructions;
 pub mod state;
+pub mod stroke_codec;
+pub mod utils;
 
 use anchor_lang::prelude::*;
 
@@ -21,27 +23,56 @@ pub mod karada {
         game_code: [u8; 6],
         stake_amount: u64,
         max_players: u8,
+        word_category: Option<u8>,
+        canvas_mode: CanvasMode,
     ) -> Result<()> {
-        instructions::initialize::handler(ctx, game_code, stake_amount, max_players)
+        instructions::initialize::handler(ctx, game_code, stake_amount, max_players, word_category, canvas_mode)
     }
 
-    pub fn join_game(ctx: Context<JoinGame>) -> Result<()> {
-        instructions::join_game::handler(ctx)
+    pub fn join_game(ctx: Context<JoinGame>, entropy_commitment: [u8; 32]) -> Result<()> {
+        instructions::join_game::handler(ctx, entropy_commitment)
     }
 
-    pub fn start_game(ctx: Context<StartGame>) -> Result<()> {
-        instructions::start_game::handler(ctx)
+    pub fn leave_game(ctx: Context<LeaveGame>) -> Result<()> {
+        instructions::leave_game::handler(ctx)
+    }
+
+    pub fn start_game<'info>(
+        ctx: Context<'_, '_, 'info, 'info, StartGame<'info>>,
+        candidates: Vec<WordCandidate>,
+    ) -> Result<()> {
+        instructions::start_game::handler(ctx, candidates)
+    }
+
+    pub fn reveal_entropy(ctx: Context<RevealEntropy>, entropy: [u8; 32]) -> Result<()> {
+        instructions::reveal_entropy::handler(ctx, entropy)
+    }
+
+    pub fn expire_seeding(ctx: Context<ExpireSeeding>) -> Result<()> {
+        instructions::cancel_game::expire_seeding_handler(ctx)
     }
 
     pub fn cancel_game(ctx: Context<CancelGame>) -> Result<()> {
         instructions::cancel_game::cancel_handler(ctx)
     }
 
+    pub fn expire_lobby(ctx: Context<ExpireLobby>) -> Result<()> {
+        instructions::cancel_game::expire_handler(ctx)
+    }
+
     pub fn refund_stake(ctx: Context<RefundStake>) -> Result<()> {
         instructions::cancel_game::refund_handler(ctx)
     }
 
+    pub fn refund_all<'info>(ctx: Context<'_, '_, 'info, 'info, RefundAll<'info>>) -> Result<()> {
+        instructions::cancel_game::refund_all_handler(ctx)
+    }
+
     // Gameplay instructions (execute in Ephemeral Rollup)
+    pub fn choose_word(ctx: Context<ChooseWord>, index: u8) -> Result<()> {
+        instructions::choose_word::handler(ctx, index)
+    }
+
     pub fn add_stroke(
         ctx: Context<AddStroke>,
         points: Vec<u16>,
@@ -51,31 +82,63 @@ pub mod karada {
         instructions::add_stroke::handler(ctx, points, color, width)
     }
 
+    pub fn add_stroke_encoded(
+        ctx: Context<AddStroke>,
+        encoded_points: Vec<u8>,
+        color: u32,
+        width: u8,
+    ) -> Result<()> {
+        instructions::add_stroke::encoded_handler(ctx, encoded_points, color, width)
+    }
+
+    pub fn add_strokes(ctx: Context<AddStroke>, strokes: Vec<StrokeInput>) -> Result<()> {
+        instructions::add_stroke::batch_handler(ctx, strokes)
+    }
+
     pub fn submit_guess(ctx: Context<SubmitGuess>, word: String) -> Result<()> {
         instructions::submit_guess::handler(ctx, word)
     }
 
+    pub fn reveal_hint(ctx: Context<RevealHint>, params: RevealHintParams) -> Result<()> {
+        instructions::reveal_hint::handler(ctx, params)
+    }
+
+    pub fn set_close_filter(ctx: Context<SetCloseFilter>, offset: u16, chunk: Vec<u8>) -> Result<()> {
+        instructions::set_close_filter::handler(ctx, offset, chunk)
+    }
+
     pub fn tick(ctx: Context<Tick>) -> Result<()> {
         instructions::tick::handler(ctx)
     }
 
-    pub fn end_round(ctx: Context<EndRound>, params: EndRoundParams) -> Result<()> {
+    pub fn end_round<'info>(
+        ctx: Context<'_, '_, 'info, 'info, EndRound<'info>>,
+        params: EndRoundParams,
+    ) -> Result<()> {
         instructions::end_round::handler(ctx, params)
     }
 
     // Finalization and payouts
-    pub fn finalize_game(
-        ctx: Context<FinalizeGame>,
-        player_scores: Vec<PlayerScore>,
+    pub fn finalize_game<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeGame<'info>>,
     ) -> Result<()> {
-        instructions::finalize_game::handler(ctx, player_scores)
+        instructions::finalize_game::handler(ctx)
     }
 
-    pub fn create_payout(ctx: Context<CreatePayout>, rank: u8) -> Result<()> {
-        instructions::claim_payout::create_payout_handler(ctx, rank)
+    pub fn create_payout(ctx: Context<CreatePayout>) -> Result<()> {
+        instructions::claim_payout::create_payout_handler(ctx)
     }
 
     pub fn claim_payout(ctx: Context<ClaimPayout>) -> Result<()> {
         instructions::claim_payout::claim_payout_handler(ctx)
     }
+
+    // Word banks
+    pub fn create_word_bank(ctx: Context<CreateWordBank>, name: [u8; 32]) -> Result<()> {
+        instructions::create_word_bank::create_handler(ctx, name)
+    }
+
+    pub fn add_words(ctx: Context<AddWords>, entries: Vec<WordEntry>) -> Result<()> {
+        instructions::create_word_bank::add_words_handler(ctx, entries)
+    }
 }
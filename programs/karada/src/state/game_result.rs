@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+// Final standings (written once by finalize_game)
+#[account]
+#[derive(InitSpace)]
+pub struct GameResult {
+    pub game_config: Pubkey,
+    #[max_len(10)]
+    pub standings: Vec<Standing>,   // Ranked best first, index = rank
+    pub total_allocated: u64,       // Sum of amounts of created Payout accounts
+    pub finalized_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct Standing {
+    pub player: Pubkey,
+    pub score: u32,
+    pub amount: u64,                // Prize for this rank, in lamports
+}
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::utils::{alternate_hash, hash_word, letters_root};
 
 // Status enum for game lifecycle
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
@@ -10,6 +12,23 @@ pub enum GameStatus {
     Cancelled,      // Game cancelled, refunds available
 }
 
+// Phases within a round
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum RoundPhase {
+    Seeding,        // Players reveal the entropy they committed to at join
+    #[default]
+    Choosing,       // Drawer picks one of the candidate words
+    Drawing,        // Word chosen, round timer running
+}
+
+// What happens once a round's canvas is full
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum CanvasMode {
+    #[default]
+    Ring,           // New strokes overwrite the oldest, events keep the full drawing
+    Paged,          // Strokes continue on a new page, up to MAX_CANVAS_PAGES, then CanvasFull
+}
+
 // Main game config (lives on mainnet, NOT delegated)
 #[account]
 #[derive(InitSpace)]
@@ -21,18 +40,51 @@ pub struct GameConfig {
     pub player_count: u8,           // Current number of joined players
     pub status: GameStatus,
     pub prize_pool: Pubkey,         // Prize pool vault
+    pub word_bank: Option<Pubkey>,  // Bank words are drawn from, None if the host supplies them
+    pub word_category: Option<u8>,  // Only draw bank words of this category
+    pub canvas_mode: CanvasMode,
     pub created_at: i64,
     pub started_at: i64,
     pub ended_at: i64,
     pub bump: u8,
 }
 
+impl GameConfig {
+    // A lobby nobody started within LOBBY_TIMEOUT can be cancelled by anyone
+    pub fn is_lobby_expired(&self, now: i64) -> bool {
+        self.status == GameStatus::Lobby && now - self.created_at >= LOBBY_TIMEOUT
+    }
+}
+
 // Active game state (lives in ER during gameplay)
 #[account]
 #[derive(InitSpace)]
 pub struct Game {
     pub config: Pubkey,             // Reference to GameConfig
-    pub current_word: [u8; 32],     // Current word being drawn (encrypted/hidden)
+    pub word_commitment: [u8; 32],  // sha256(salt || word), salt and word revealed at round end
+    #[max_len(2)]
+    pub word_alternates: Vec<[u8; 8]>, // Other accepted answers, see utils::alternate_hash
+    pub reserved_words: u64,        // Bitmask of word bank entries set aside for this game
+    pub used_words: u64,            // Bitmask of word bank entries already drawn
+    pub seed: [u8; 32],             // Game seed from every player's entropy, drives drawer order
+    pub seeding_deadline: i64,      // Game can be cancelled if entropy is still missing after this
+    pub entropy_reveals: u8,        // Players who revealed their entropy so far
+    pub round_seed: [u8; 32],       // Seed the current round's word was drawn with
+    pub round_phase: RoundPhase,
+    #[max_len(3)]
+    pub candidates: Vec<WordCandidate>, // Words offered to the drawer while Choosing
+    pub choosing_deadline: i64,     // After this anyone can trigger a random pick
+    pub word_difficulty: u8,        // Difficulty of the chosen word, scales points
+    pub word_bank_index: Option<u8>, // Bank entry of the current word, whose salt is public. None for host words.
+    pub letters_root: [u8; 32],     // Merkle root over the word's letters, zero if hints are off
+    #[max_len(128)]
+    pub word_hint: String,          // Word shape with revealed letters, e.g. "_a___ ___-__"
+    pub hints_revealed: u8,
+    #[max_len(2048)]
+    pub close_filter: Vec<u8>,      // Bloom filter of near misses keyed by round_seed, empty until the first chunk is posted
+    pub canvas_width: u16,          // Stroke coordinates must lie within these bounds
+    pub canvas_height: u16,
+    pub canvas_page: u8,            // Page strokes are written to this round, always 0 for ring canvases
     pub current_drawer_index: u8,   // Index into players array
     pub current_round: u8,          // 0-indexed round number
     pub round_start_time: i64,
@@ -40,9 +92,147 @@ pub struct Game {
     pub time_remaining: i64,
     #[max_len(10)]
     pub players: Vec<Pubkey>,       // Ordered list of players
+    pub completed: bool,            // Set once every player has drawn
     pub bump: u8,
 }
 
+impl Game {
+    // Seconds left in the current round, derived from the clock rather than the tick crank.
+    // The timer only starts once the drawer has chosen a word.
+    pub fn time_remaining_at(&self, now: i64) -> i64 {
+        if self.round_phase != RoundPhase::Drawing {
+            return self.round_duration;
+        }
+        (self.round_duration - (now - self.round_start_time)).max(0)
+    }
+
+    // Offers the candidates to the current drawer. A single candidate is chosen right away.
+    pub fn begin_choosing(&mut self, candidates: Vec<WordCandidate>, now: i64) -> Result<()> {
+        require!(
+            !candidates.is_empty() && candidates.len() <= WORD_CANDIDATES,
+            crate::error::ErrorCode::InvalidWord
+        );
+        for candidate in candidates.iter() {
+            require!(
+                (1..=MAX_WORD_DIFFICULTY).contains(&candidate.difficulty),
+                crate::error::ErrorCode::InvalidWord
+            );
+            require!(
+                is_valid_shape(&candidate.word.shape),
+                crate::error::ErrorCode::InvalidWord
+            );
+            require!(
+                candidate.word.alternates.len() <= MAX_WORD_ALTERNATES,
+                crate::error::ErrorCode::InvalidWord
+            );
+        }
+
+        self.candidates = candidates;
+        self.round_phase = RoundPhase::Choosing;
+        self.choosing_deadline = now + CHOOSE_DURATION;
+
+        if self.candidates.len() == 1 {
+            self.choose_word(0, now)?;
+        }
+
+        Ok(())
+    }
+
+    // Installs the chosen candidate and starts the round timer
+    pub fn choose_word(&mut self, index: usize, now: i64) -> Result<()> {
+        require!(
+            self.round_phase == RoundPhase::Choosing,
+            crate::error::ErrorCode::WordAlreadyChosen
+        );
+        let candidate = self.candidates.get(index)
+            .cloned()
+            .ok_or(crate::error::ErrorCode::InvalidWord)?;
+
+        if let Some(bank_index) = candidate.bank_index {
+            self.used_words |= 1u64 << bank_index;
+        }
+
+        self.word_commitment = candidate.word.commitment;
+        self.word_alternates = candidate.word.alternates;
+        self.word_difficulty = candidate.difficulty;
+        self.word_bank_index = candidate.bank_index;
+        self.letters_root = candidate.word.letters_root;
+        self.word_hint = candidate.word.shape;
+        self.hints_revealed = 0;
+        self.close_filter = vec![];
+        self.canvas_page = 0;
+        self.candidates = vec![];
+        self.round_phase = RoundPhase::Drawing;
+        self.round_start_time = now;
+        self.time_remaining = self.round_duration;
+
+        Ok(())
+    }
+
+    // After this anyone can end the round without a reveal, voiding it
+    pub fn reveal_deadline(&self) -> i64 {
+        self.round_start_time + self.round_duration + REVEAL_GRACE
+    }
+
+    // Whether a normalized guess is the word or one of its accepted alternates
+    pub fn accepts(&self, salt: &[u8; 32], word: &str) -> bool {
+        hash_word(salt, word) == self.word_commitment
+            || self.word_alternates.contains(&alternate_hash(salt, word))
+    }
+
+    // Time-decay points for a correct guess made at `timestamp`, harder words are worth more
+    pub fn guess_points(&self, timestamp: i64) -> u32 {
+        let elapsed = (timestamp - self.round_start_time).clamp(0, self.round_duration);
+        let time_factor = self.round_duration - elapsed;
+        let points_range = MAX_POINTS - MIN_POINTS;
+        let base_points = MIN_POINTS + ((points_range as i64 * time_factor) / self.round_duration) as u32;
+
+        let difficulty_bps = *DIFFICULTY_POINTS_BPS
+            .get(self.word_difficulty.saturating_sub(1) as usize)
+            .unwrap_or(&10000) as u32;
+        base_points * difficulty_bps / 10000
+    }
+
+    // Checks a revealed (normalized) word and salt against everything committed for the round
+    pub fn verify_reveal(&self, word: &str, salt: &[u8; 32], letters_secret: &[u8; 32]) -> bool {
+        let shape_matches = word.chars().count() == self.word_hint.chars().count()
+            && word.chars().zip(self.word_hint.chars()).all(|(letter, hint)| match hint {
+                ' ' | '-' => letter == hint,
+                '_' => letter != ' ' && letter != '-',
+                _ => letter == hint, // Revealed hint letter
+            });
+        let letters_match = self.letters_root == [0u8; 32]
+            || letters_root(letters_secret, word) == self.letters_root;
+
+        hash_word(salt, word) == self.word_commitment
+            && shape_matches
+            && letters_match
+    }
+
+    // Rejects strokes the renderer can't draw
+    pub fn validate_stroke(&self, points: &[u16], max_points: usize, color: u32, width: u8) -> Result<()> {
+        require!(
+            !points.is_empty() && points.chunks_exact(2).remainder().is_empty(),
+            crate::error::ErrorCode::InvalidStrokePoints
+        );
+        require!(
+            points.len() <= 2 * max_points,
+            crate::error::ErrorCode::TooManyStrokePoints
+        );
+        require!(
+            points.chunks_exact(2).all(|p| p[0] < self.canvas_width && p[1] < self.canvas_height),
+            crate::error::ErrorCode::StrokeOutOfBounds
+        );
+        require!(
+            (MIN_STROKE_WIDTH..=MAX_STROKE_WIDTH).contains(&width),
+            crate::error::ErrorCode::InvalidStrokeWidth
+        );
+        require!(color <= 0x00FF_FFFF, crate::error::ErrorCode::InvalidStrokeColor);
+
+        Ok(())
+    }
+}
+
 // Per-player state (lives in ER during gameplay)
 #[account]
 #[derive(InitSpace)]
@@ -50,27 +240,96 @@ pub struct PlayerState {
     pub game_config: Pubkey,
     pub player: Pubkey,
     pub score: u32,
-    pub has_guessed_current_round: bool,
+    pub has_guessed_current_round: bool, // Set while end_round scores the round's guesses
     pub is_active: bool,            // False if disconnected
     pub joined_at: i64,
+    pub entropy_commitment: [u8; 32], // utils::entropy_commitment of the player's seed contribution
+    pub entropy_revealed: bool,
     pub bump: u8,
 }
 
-// Drawing canvas for current round (lives in ER)
-#[account]
+// Commitment to a round's word, supplied by the host
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct WordCommitment {
+    pub commitment: [u8; 32],       // sha256(salt || normalize_word(word)), fresh salt kept by the host until end_round
+    // Shape shown to guessers: '_' per letter, spaces and hyphens as is
+    #[max_len(32)]
+    pub shape: String,
+    // Optional, see utils::letters_root. Zero disables letter hints.
+    pub letters_root: [u8; 32],
+    // Other accepted answers as alternate_hash(salt, alternate). Only the
+    // canonical word is revealed and shown as a hint.
+    #[max_len(2)]
+    pub alternates: Vec<[u8; 8]>,
+}
+
+// Word offered to the drawer at the start of a round
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct WordCandidate {
+    pub word: WordCommitment,
+    pub difficulty: u8,             // 1 (easy) to MAX_WORD_DIFFICULTY
+    pub bank_index: Option<u8>,     // Entry in the game's word bank, None for host words
+}
+
+// Drawing canvas for current round (lives in ER). Zero-copy so appending a
+// stroke only touches that stroke's bytes. Strokes and their points are kept in
+// ring buffers: stroke n lives in strokes[n % CANVAS_STROKE_SLOTS] and its
+// points start at points[point_offset % CANVAS_POINT_SLOTS], wrapping around.
+// Overwritten strokes are gone from the account; StrokeAdded and StrokesAdded
+// carry every point so indexers can keep the full drawing. Stroke n is still stored if n + CANVAS_STROKE_SLOTS >= stroke_count and
+// point_offset + CANVAS_POINT_SLOTS >= points_written.
+// Paged games never wrap: a batch that doesn't fit goes to the next page, a
+// separate account, and nothing is overwritten.
+#[account(zero_copy)]
 pub struct DrawingCanvas {
     pub game: Pubkey,
+    pub stroke_count: u64,          // Strokes added to this page, including overwritten ones
+    pub points_written: u64,        // Coordinates added to this page, including overwritten ones
     pub round: u8,
-    pub strokes: Vec<DrawStroke>,
+    pub page: u8,                   // Always 0 for ring canvases
     pub bump: u8,
+    pub _padding: [u8; 5],
+    pub strokes: [DrawStroke; CANVAS_STROKE_SLOTS],
+    pub points: [u16; CANVAS_POINT_SLOTS], // Coordinates [x1,y1,x2,y2,...] of all strokes
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+impl DrawingCanvas {
+    pub const SPACE: usize = 8 + std::mem::size_of::<DrawingCanvas>();
+
+    // Whether a page can take these strokes without overwriting anything
+    pub fn has_room(&self, strokes: usize, coordinates: usize) -> bool {
+        self.stroke_count as usize + strokes <= CANVAS_STROKE_SLOTS
+            && self.points_written as usize + coordinates <= CANVAS_POINT_SLOTS
+    }
+
+    // Appends a validated stroke, overwriting the oldest data once the buffers are full
+    pub fn push_stroke(&mut self, points: &[u16], color: u32, width: u8, timestamp: i64) {
+        let point_offset = self.points_written;
+        for (k, value) in points.iter().enumerate() {
+            self.points[((point_offset + k as u64) % CANVAS_POINT_SLOTS as u64) as usize] = *value;
+        }
+
+        self.strokes[(self.stroke_count % CANVAS_STROKE_SLOTS as u64) as usize] = DrawStroke {
+            timestamp,
+            point_offset,
+            color,
+            point_count: points.len() as u16,
+            width,
+            _padding: 0,
+        };
+        self.stroke_count += 1;
+        self.points_written += points.len() as u64;
+    }
+}
+
+#[zero_copy]
 pub struct DrawStroke {
-    pub points: Vec<u16>,           // Compressed coordinates [x1,y1,x2,y2,...]
-    pub color: u32,                 // RGB color
-    pub width: u8,                  // Line width 1-20
     pub timestamp: i64,
+    pub point_offset: u64,          // Position of the first coordinate in the points ring
+    pub color: u32,                 // RGB color, no alpha
+    pub point_count: u16,           // Number of u16 coordinates, within the game's canvas
+    pub width: u8,                  // Line width 1-20
+    pub _padding: u8,
 }
 
 // Guess log for current round (lives in ER)
@@ -78,15 +337,61 @@ pub struct DrawStroke {
 pub struct GuessLog {
     pub game: Pubkey,
     pub round: u8,
-    pub guesses: Vec<Guess>,
+    pub guesses: Vec<Guess>,        // At most MAX_GUESSES_PER_ROUND per player
+    pub revealed_word: Option<String>, // Normalized word, set by end_round once it is public
     pub bump: u8,
 }
 
+impl GuessLog {
+    pub const SPACE: usize = 8 + 32 + 1
+        + 4 + MAX_LOGGED_GUESSES * (32 + (4 + MAX_WORD_BYTES) + 8 + 1 + 4)
+        + (1 + 4 + MAX_WORD_BYTES)
+        + 1;
+
+    pub fn guesses_by(&self, player: &Pubkey) -> usize {
+        self.guesses.iter().filter(|guess| guess.player == *player).count()
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct Guess {
     pub player: Pubkey,
-    pub word: String,               // The guessed word
+    pub word: String,               // The normalized guess
     pub timestamp: i64,             // When guess was made
-    pub correct: bool,
+    pub correct: bool,              // Set by end_round once the salt is known
     pub points_awarded: u32,
 }
+
+// Shapes only use '_' for letters plus spaces and hyphens
+pub fn is_valid_shape(shape: &str) -> bool {
+    !shape.is_empty()
+        && shape.len() <= MAX_WORD_LEN
+        && shape.chars().all(|c| matches!(c, '_' | ' ' | '-'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canvas_page_fills_up_without_wrapping() {
+        let mut canvas: DrawingCanvas = bytemuck::Zeroable::zeroed();
+        let stroke = [1u16; 2 * MAX_STROKE_POINTS];
+
+        // Point slots run out first with full strokes
+        let mut written = 0;
+        while canvas.has_room(1, stroke.len()) {
+            canvas.push_stroke(&stroke, 0, 1, 0);
+            written += 1;
+        }
+        assert_eq!(written, CANVAS_POINT_SLOTS / stroke.len());
+        assert!(canvas.points_written as usize <= CANVAS_POINT_SLOTS);
+
+        // Stroke slots run out first with single points
+        let mut canvas: DrawingCanvas = bytemuck::Zeroable::zeroed();
+        assert!(canvas.has_room(CANVAS_STROKE_SLOTS, 2 * CANVAS_STROKE_SLOTS));
+        assert!(!canvas.has_room(CANVAS_STROKE_SLOTS + 1, 2 * CANVAS_STROKE_SLOTS + 2));
+        canvas.push_stroke(&[0, 0], 0, 1, 0);
+        assert!(!canvas.has_room(CANVAS_STROKE_SLOTS, 2 * CANVAS_STROKE_SLOTS));
+    }
+}
@@ -1,5 +1,9 @@
 pub mod payout;
 pub mod game_state;
+pub mod game_result;
+pub mod word_bank;
 
 pub use payout::*;
 pub use game_state::*;
+pub use game_result::*;
+pub use word_bank::*;
@@ -6,6 +6,7 @@ use anchor_lang::prelude::*;
 pub struct Payout {
     pub game_config: Pubkey,
     pub player: Pubkey,
+    pub rank: u8,
     pub amount: u64,
     pub claimed: bool,
     pub created_at: i64,
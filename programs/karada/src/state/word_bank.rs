@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::utils::random_index;
+
+// Themed word list that games can draw from (owned by an admin or creator)
+#[account]
+#[derive(InitSpace)]
+pub struct WordBank {
+    pub authority: Pubkey,          // Only key allowed to add words
+    pub name: [u8; 32],             // Bank name, part of the PDA seeds
+    #[max_len(64)]
+    pub entries: Vec<WordEntry>,    // At most 64, see Game.used_words
+    // Entries already handed to a game. A word is public once it has been
+    // revealed or guessed in plain text, so each entry is only ever used once.
+    pub retired: u64,
+    pub bump: u8,
+}
+
+// Banks are public word lists. The salt is stored next to the commitment so
+// bank rounds can be scored without a reveal, which means anyone can recover
+// every entry by hashing a dictionary, and with the game seed work out which
+// words each round will offer. Use host words when the answers must stay hidden.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct WordEntry {
+    pub commitment: [u8; 32],       // sha256(salt || normalize_word(word))
+    pub salt: [u8; 32],
+    #[max_len(32)]
+    pub shape: String,              // See WordCommitment.shape
+    pub letters_root: [u8; 32],
+    #[max_len(2)]
+    pub alternates: Vec<[u8; 8]>,   // See WordCommitment.alternates
+    pub category: u8,
+    pub difficulty: u8,             // 1 (easy) to MAX_WORD_DIFFICULTY
+}
+
+impl WordBank {
+    // Index of the entry picked by `seed` among those matching `category` and not in `excluded`
+    pub fn draw(&self, category: Option<u8>, excluded: u64, seed: &[u8; 32]) -> Option<usize> {
+        let eligible: Vec<usize> = (0..self.entries.len())
+            .filter(|i| self.is_eligible(*i, category, excluded))
+            .collect();
+        if eligible.is_empty() {
+            return None;
+        }
+
+        Some(eligible[random_index(seed, eligible.len())])
+    }
+
+    // Retires up to `count` unused entries for one game and returns them as a bitmask
+    pub fn reserve(&mut self, category: Option<u8>, count: usize, seed: &[u8; 32]) -> u64 {
+        let mut reserved = 0u64;
+        for k in 0..count {
+            let pick_seed = hashv(&[seed, &(k as u16).to_le_bytes()]).to_bytes();
+            let Some(index) = self.draw(category, self.retired | reserved, &pick_seed) else {
+                break;
+            };
+            reserved |= 1u64 << index;
+        }
+        self.retired |= reserved;
+        reserved
+    }
+
+    pub fn eligible_count(&self, category: Option<u8>, excluded: u64) -> usize {
+        (0..self.entries.len())
+            .filter(|i| self.is_eligible(*i, category, excluded))
+            .count()
+    }
+
+    fn is_eligible(&self, index: usize, category: Option<u8>, excluded: u64) -> bool {
+        let in_category = match category {
+            Some(c) => self.entries[index].category == c,
+            None => true,
+        };
+        excluded & (1u64 << index) == 0 && in_category
+    }
+}
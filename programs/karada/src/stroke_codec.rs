@@ -0,0 +1,215 @@
+// Compact stroke encoding shared by the program, clients and indexers.
+//
+// A stroke [x1,y1,x2,y2,...] is encoded as x1 and y1 as unsigned LEB128 varints,
+// then for every following point dx and dy as zig-zag varints. Nearby points of a
+// smooth curve take one byte per coordinate instead of two.
+
+// A u16 never needs more than 3 varint bytes
+const MAX_VARINT_BYTES: usize = 3;
+
+// None if points isn't a list of (x, y) pairs
+pub fn encode_points(points: &[u16]) -> Option<Vec<u8>> {
+    if !points.chunks_exact(2).remainder().is_empty() {
+        return None;
+    }
+
+    let mut encoded = Vec::with_capacity(points.len());
+    let mut previous: Option<(u16, u16)> = None;
+
+    for point in points.chunks_exact(2) {
+        let (x, y) = (point[0], point[1]);
+        match previous {
+            None => {
+                write_varint(&mut encoded, x as u32);
+                write_varint(&mut encoded, y as u32);
+            }
+            Some((px, py)) => {
+                write_varint(&mut encoded, zigzag(x as i32 - px as i32));
+                write_varint(&mut encoded, zigzag(y as i32 - py as i32));
+            }
+        }
+        previous = Some((x, y));
+    }
+
+    Some(encoded)
+}
+
+// None if the data ends inside a point, has a varint that isn't minimally
+// encoded, leaves the u16 range or holds more than max_points points. There is
+// no length prefix, so data cut at a point boundary decodes as a shorter stroke.
+pub fn decode_points(data: &[u8], max_points: usize) -> Option<Vec<u16>> {
+    let mut points = Vec::new();
+    let mut rest = data;
+    let mut previous: Option<(u16, u16)> = None;
+
+    while !rest.is_empty() {
+        if points.len() >= 2 * max_points {
+            return None;
+        }
+
+        let (first, after_first) = read_varint(rest)?;
+        let (second, after_second) = read_varint(after_first)?;
+        rest = after_second;
+
+        let (x, y) = match previous {
+            None => (first as i32, second as i32),
+            Some((px, py)) => (px as i32 + unzigzag(first), py as i32 + unzigzag(second)),
+        };
+        let x = u16::try_from(x).ok()?;
+        let y = u16::try_from(y).ok()?;
+
+        points.push(x);
+        points.push(y);
+        previous = Some((x, y));
+    }
+
+    Some(points)
+}
+
+fn zigzag(delta: i32) -> u32 {
+    ((delta << 1) ^ (delta >> 31)) as u32
+}
+
+fn unzigzag(value: u32) -> i32 {
+    (value >> 1) as i32 ^ -((value & 1) as i32)
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u32) {
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint(data: &[u8]) -> Option<(u32, &[u8])> {
+    let mut value = 0u32;
+    for (i, byte) in data.iter().take(MAX_VARINT_BYTES).enumerate() {
+        value |= ((byte & 0x7f) as u32) << (7 * i);
+        if byte & 0x80 == 0 {
+            // A zero final byte after a continuation means the varint is overlong
+            if i > 0 && *byte == 0 {
+                return None;
+            }
+            return Some((value, &data[i + 1..]));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_a_curve() {
+        let points: Vec<u16> = (0..25).flat_map(|i| [100 + 3 * i, 200 + 2 * i]).collect();
+        let encoded = encode_points(&points).unwrap();
+
+        assert!(encoded.len() < points.len() * 2);
+        assert_eq!(decode_points(&encoded, 25), Some(points));
+    }
+
+    #[test]
+    fn round_trips_extreme_coordinates() {
+        let points = vec![0, 0, u16::MAX, u16::MAX, 0, u16::MAX, u16::MAX, 0];
+        let encoded = encode_points(&points).unwrap();
+
+        assert_eq!(decode_points(&encoded, 4), Some(points));
+    }
+
+    #[test]
+    fn empty_input_is_empty_stroke() {
+        assert_eq!(encode_points(&[]), Some(vec![]));
+        assert_eq!(decode_points(&[], 25), Some(vec![]));
+    }
+
+    #[test]
+    fn rejects_odd_point_lists() {
+        assert_eq!(encode_points(&[1, 2, 3]), None);
+    }
+
+    #[test]
+    fn rejects_truncated_input() {
+        // (300, 400) takes two bytes per coordinate, the (+10, -10) delta one each
+        let encoded = encode_points(&[300, 400, 310, 390]).unwrap();
+        assert_eq!(encoded.len(), 6);
+
+        for len in [1, 2, 3, 5] {
+            assert_eq!(decode_points(&encoded[..len], 25), None, "prefix of {} bytes", len);
+        }
+        assert_eq!(decode_points(&encoded[..4], 25), Some(vec![300, 400]));
+    }
+
+    #[test]
+    fn rejects_overlong_varints() {
+        // 0 and 5 written with a redundant continuation byte
+        assert_eq!(decode_points(&[0x80, 0x00, 0x05], 25), None);
+        assert_eq!(decode_points(&[0x00, 0x85, 0x00], 25), None);
+        // More than three bytes
+        assert_eq!(decode_points(&[0x80, 0x80, 0x80, 0x01, 0x00], 25), None);
+    }
+
+    #[test]
+    fn rejects_out_of_range_deltas() {
+        // Starts at (0, 0) and moves by -1
+        let mut below_zero = vec![0x00, 0x00];
+        write_varint(&mut below_zero, zigzag(-1));
+        write_varint(&mut below_zero, zigzag(0));
+        assert_eq!(decode_points(&below_zero, 25), None);
+
+        // Starts at (u16::MAX, 0) and moves by +1
+        let mut above_max = vec![];
+        write_varint(&mut above_max, u16::MAX as u32);
+        write_varint(&mut above_max, 0);
+        write_varint(&mut above_max, zigzag(1));
+        write_varint(&mut above_max, zigzag(0));
+        assert_eq!(decode_points(&above_max, 25), None);
+
+        // First point beyond u16
+        let mut absolute = vec![];
+        write_varint(&mut absolute, u16::MAX as u32 + 1);
+        write_varint(&mut absolute, 0);
+        assert_eq!(decode_points(&absolute, 25), None);
+    }
+
+    #[test]
+    fn rejects_too_many_points() {
+        let points: Vec<u16> = (0..10).flat_map(|i| [i, i]).collect();
+        let encoded = encode_points(&points).unwrap();
+
+        assert_eq!(decode_points(&encoded, 10), Some(points));
+        assert_eq!(decode_points(&encoded, 9), None);
+    }
+
+    #[test]
+    fn zigzag_round_trips() {
+        for delta in [-65535, -64, -1, 0, 1, 63, 64, 65535] {
+            assert_eq!(unzigzag(zigzag(delta)), delta);
+        }
+    }
+
+    #[test]
+    fn long_curve_fits_one_transaction() {
+        use crate::constants::*;
+
+        // An outward spiral across most of the canvas, sampled every few pixels
+        let points: Vec<u16> = (0..MAX_ENCODED_STROKE_POINTS)
+            .flat_map(|i| {
+                let t = i as f64 * 0.05;
+                let radius = 20.0 + 0.6 * i as f64;
+                [(400.0 + radius * t.cos()).round() as u16, (300.0 + radius * t.sin()).round() as u16]
+            })
+            .collect();
+        assert!(points.iter().step_by(2).all(|x| *x < CANVAS_WIDTH));
+        assert!(points.iter().skip(1).step_by(2).all(|y| *y < CANVAS_HEIGHT));
+
+        // Raw, this is far more than add_stroke takes and would not fit the same budget
+        assert!(points.len() / 2 > MAX_STROKE_POINTS);
+        assert!(points.len() * 2 > MAX_ENCODED_STROKE_BYTES);
+
+        let encoded = encode_points(&points).unwrap();
+        assert!(encoded.len() <= MAX_ENCODED_STROKE_BYTES, "{} bytes", encoded.len());
+        assert_eq!(decode_points(&encoded, MAX_ENCODED_STROKE_POINTS), Some(points));
+    }
+}
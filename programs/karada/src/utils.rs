@@ -0,0 +1,514 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+use crate::{Game, GameConfig, PlayerState, PrizePool, WordBank, WordCandidate, WordCommitment};
+use crate::constants::*;
+
+// Loads a PlayerState passed via remaining accounts and checks that it is the
+// canonical PDA for this game (owner and discriminator are checked by try_from)
+pub fn load_player_state<'info>(
+    info: &'info AccountInfo<'info>,
+    game_config: &Pubkey,
+) -> Result<Account<'info, PlayerState>> {
+    let player_state = Account::<PlayerState>::try_from(info)?;
+
+    require_keys_eq!(
+        player_state.game_config,
+        *game_config,
+        crate::error::ErrorCode::InvalidPlayerState
+    );
+
+    let expected = Pubkey::create_program_address(
+        &[
+            PLAYER_STATE_SEED,
+            game_config.as_ref(),
+            player_state.player.as_ref(),
+            &[player_state.bump],
+        ],
+        &crate::ID,
+    )
+    .map_err(|_| crate::error::ErrorCode::InvalidPlayerState)?;
+
+    require_keys_eq!(
+        expected,
+        info.key(),
+        crate::error::ErrorCode::InvalidPlayerState
+    );
+
+    Ok(player_state)
+}
+
+// Loads the PlayerState of every roster member, in roster order, rejecting
+// missing, extra or out-of-order accounts
+pub fn load_roster<'info>(
+    infos: &'info [AccountInfo<'info>],
+    game_config: &Pubkey,
+    players: &[Pubkey],
+) -> Result<Vec<Account<'info, PlayerState>>> {
+    require!(
+        infos.len() == players.len(),
+        crate::error::ErrorCode::InvalidPlayerCount
+    );
+
+    let mut roster = Vec::with_capacity(infos.len());
+    for (info, player) in infos.iter().zip(players.iter()) {
+        let player_state = load_player_state(info, game_config)?;
+        require_keys_eq!(
+            player_state.player,
+            *player,
+            crate::error::ErrorCode::InvalidPlayerState
+        );
+        roster.push(player_state);
+    }
+
+    Ok(roster)
+}
+
+// Moves lamports out of the prize pool. The pool is a program-owned account with
+// data, so the System Program refuses to debit it and we adjust balances directly.
+// Callers are responsible for updating the pool totals.
+pub fn withdraw_from_pool<'info>(
+    prize_pool: &Account<'info, PrizePool>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let pool_info = prize_pool.to_account_info();
+
+    // Keep the pool rent-exempt while it still holds state
+    let rent_exempt = Rent::get()?.minimum_balance(pool_info.data_len());
+    let available = pool_info.lamports().saturating_sub(rent_exempt);
+    require!(
+        amount <= available,
+        crate::error::ErrorCode::InsufficientPoolFunds
+    );
+
+    **pool_info.try_borrow_mut_lamports()? -= amount;
+    let to_lamports = to.lamports()
+        .checked_add(amount)
+        .ok_or(crate::error::ErrorCode::ArithmeticOverflow)?;
+    **to.try_borrow_mut_lamports()? = to_lamports;
+
+    Ok(())
+}
+
+// Canonical form of a word, applied to guesses and reveals on-chain and by hosts
+// before committing: diacritics stripped, lowercase, punctuation other than
+// hyphens removed, whitespace collapsed, NFC. Only the Latin combining
+// diacritics block (U+0300-U+036F) is stripped; other combining marks, such as
+// kana voicing marks, viramas and Thai vowel signs, are kept so distinct words
+// in those scripts stay distinct.
+pub fn normalize_word(input: &str) -> Result<String> {
+    require!(input.len() <= MAX_GUESS_BYTES, crate::error::ErrorCode::InvalidWord);
+
+    let stripped: String = input.nfd()
+        .filter(|c| !('\u{0300}'..='\u{036F}').contains(c))
+        .flat_map(char::to_lowercase)
+        .filter(|c| c.is_alphanumeric() || is_combining_mark(*c) || c.is_whitespace() || *c == '-')
+        .collect();
+    let normalized: String = stripped.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .nfc()
+        .collect();
+
+    let len = normalized.chars().count();
+    require!(len > 0 && len <= MAX_WORD_LEN, crate::error::ErrorCode::InvalidWord);
+
+    Ok(normalized)
+}
+
+// Commitment to a word under a round salt, as stored in Game.word_commitment
+pub fn hash_word(salt: &[u8; 32], word: &str) -> [u8; 32] {
+    hashv(&[salt, word.as_bytes()]).to_bytes()
+}
+
+// Accepted alternate answer (synonym, abbreviation), truncated to keep word banks small
+pub fn alternate_hash(salt: &[u8; 32], word: &str) -> [u8; 8] {
+    let mut truncated = [0u8; 8];
+    truncated.copy_from_slice(&hash_word(salt, word)[..8]);
+    truncated
+}
+
+// Candidate words for the next round: drawn from the game's word bank when it
+// has one, otherwise supplied by the host
+pub fn next_candidates(
+    game: &Game,
+    game_config: &GameConfig,
+    word_bank: Option<&Account<WordBank>>,
+    host_candidates: Vec<WordCandidate>,
+    seed: &[u8; 32],
+) -> Result<Vec<WordCandidate>> {
+    let Some(bank_key) = game_config.word_bank else {
+        require!(
+            host_candidates.iter().all(|candidate| candidate.bank_index.is_none()),
+            crate::error::ErrorCode::InvalidWord
+        );
+        return Ok(host_candidates);
+    };
+
+    let word_bank = word_bank.ok_or(crate::error::ErrorCode::InvalidWordBank)?;
+    require_keys_eq!(
+        word_bank.key(),
+        bank_key,
+        crate::error::ErrorCode::InvalidWordBank
+    );
+    require!(host_candidates.is_empty(), crate::error::ErrorCode::InvalidWord);
+
+    // Distinct entries from those reserved at start; only the chosen one is marked used
+    let mut drawn = !game.reserved_words | game.used_words;
+    let mut candidates = Vec::with_capacity(WORD_CANDIDATES);
+    for k in 0..WORD_CANDIDATES {
+        let pick_seed = hashv(&[seed, &[k as u8]]).to_bytes();
+        let Some(index) = word_bank.draw(game_config.word_category, drawn, &pick_seed) else {
+            break;
+        };
+        drawn |= 1u64 << index;
+
+        let entry = &word_bank.entries[index];
+        candidates.push(WordCandidate {
+            word: WordCommitment {
+                commitment: entry.commitment,
+                shape: entry.shape.clone(),
+                letters_root: entry.letters_root,
+                alternates: entry.alternates.clone(),
+            },
+            difficulty: entry.difficulty,
+            bank_index: Some(index as u8),
+        });
+    }
+    require!(!candidates.is_empty(), crate::error::ErrorCode::WordBankExhausted);
+
+    Ok(candidates)
+}
+
+// What a player commits to at join, bound to their key so nobody can copy
+// another player's commitment and cancel out their entropy
+pub fn entropy_commitment(player: &Pubkey, entropy: &[u8; 32]) -> [u8; 32] {
+    hashv(&[b"commit", player.as_ref(), entropy]).to_bytes()
+}
+
+// Folds a revealed contribution into the game seed. XOR makes the result
+// independent of reveal order.
+pub fn mix_entropy(seed: &mut [u8; 32], player: &Pubkey, entropy: &[u8; 32]) {
+    let contribution = hashv(&[b"seed", player.as_ref(), entropy]).to_bytes();
+    for (byte, other) in seed.iter_mut().zip(contribution.iter()) {
+        *byte ^= other;
+    }
+}
+
+// Uniform-enough index in 0..n from a 32-byte seed
+pub fn random_index(seed: &[u8; 32], n: usize) -> usize {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&seed[..8]);
+    (u64::from_le_bytes(bytes) % n as u64) as usize
+}
+
+// Letter hints commit to each character separately so one can be revealed
+// without the rest. Leaf i is hash(nonce_i || i || letter_i) with
+// nonce_i = hash(letters_secret || i): a revealed hint exposes only its own
+// nonce, so sibling leaves in the proof can't be brute forced letter by letter.
+pub fn letter_nonce(letters_secret: &[u8; 32], position: u8) -> [u8; 32] {
+    hashv(&[letters_secret, &[position]]).to_bytes()
+}
+
+pub fn letter_leaf(nonce: &[u8; 32], position: u8, letter: &str) -> [u8; 32] {
+    hashv(&[nonce, &[position], letter.as_bytes()]).to_bytes()
+}
+
+// Pairs are hashed in sorted order so proofs don't need left/right flags
+fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        hashv(&[a, b]).to_bytes()
+    } else {
+        hashv(&[b, a]).to_bytes()
+    }
+}
+
+// Merkle root over the letters of `word`; an odd node is carried up unchanged
+pub fn letters_root(letters_secret: &[u8; 32], word: &str) -> [u8; 32] {
+    let mut level: Vec<[u8; 32]> = word.chars()
+        .enumerate()
+        .map(|(i, letter)| {
+            let mut buf = [0u8; 4];
+            letter_leaf(&letter_nonce(letters_secret, i as u8), i as u8, letter.encode_utf8(&mut buf))
+        })
+        .collect();
+    if level.is_empty() {
+        return [0u8; 32];
+    }
+
+    while level.len() > 1 {
+        level = level.chunks(2)
+            .map(|pair| match pair {
+                [a, b] => hash_pair(a, b),
+                [a] => *a,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    level[0]
+}
+
+pub fn verify_letter_proof(root: &[u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+    proof.iter().fold(leaf, |node, sibling| hash_pair(&node, sibling)) == *root
+}
+
+// Bit positions of `variant` in a close-guess filter for the given round seed
+pub fn close_filter_bits(round_seed: &[u8; 32], variant: &str) -> [usize; CLOSE_FILTER_HASHES] {
+    let digest = hashv(&[round_seed, b"close", variant.as_bytes()]).to_bytes();
+    let mut bits = [0usize; CLOSE_FILTER_HASHES];
+    for (k, bit) in bits.iter_mut().enumerate() {
+        let value = u16::from_le_bytes([digest[2 * k], digest[2 * k + 1]]) as usize;
+        *bit = value % (CLOSE_FILTER_BYTES * 8);
+    }
+    bits
+}
+
+// Hosts build the filter off-chain by inserting every spelling within edit
+// distance 1 of the word (deletions, substitutions, insertions, swaps)
+pub fn close_filter_insert(filter: &mut [u8], round_seed: &[u8; 32], variant: &str) {
+    for bit in close_filter_bits(round_seed, variant) {
+        filter[bit / 8] |= 1 << (bit % 8);
+    }
+}
+
+pub fn close_filter_contains(filter: &[u8], round_seed: &[u8; 32], variant: &str) -> bool {
+    filter.len() == CLOSE_FILTER_BYTES
+        && close_filter_bits(round_seed, variant).iter().all(|bit| filter[bit / 8] & (1 << (bit % 8)) != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LETTERS_SECRET: [u8; 32] = [7; 32];
+
+    fn leaves(word: &str) -> Vec<[u8; 32]> {
+        word.chars()
+            .enumerate()
+            .map(|(i, letter)| {
+                let mut buf = [0u8; 4];
+                letter_leaf(&letter_nonce(&LETTERS_SECRET, i as u8), i as u8, letter.encode_utf8(&mut buf))
+            })
+            .collect()
+    }
+
+    // Siblings from leaf `position` up to the root, skipping levels where the node is carried up
+    fn proof(word: &str, position: usize) -> Vec<[u8; 32]> {
+        let mut level = leaves(word);
+        let mut index = position;
+        let mut proof = vec![];
+        while level.len() > 1 {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            level = level.chunks(2)
+                .map(|pair| if pair.len() == 2 { hash_pair(&pair[0], &pair[1]) } else { pair[0] })
+                .collect();
+            index /= 2;
+        }
+        proof
+    }
+
+    #[test]
+    fn letters_root_of_even_and_odd_words() {
+        let even = leaves("ab");
+        assert_eq!(letters_root(&LETTERS_SECRET, "ab"), hash_pair(&even[0], &even[1]));
+
+        // The third leaf has no sibling and is carried up to be paired with (a, b)
+        let odd = leaves("abc");
+        assert_eq!(
+            letters_root(&LETTERS_SECRET, "abc"),
+            hash_pair(&hash_pair(&odd[0], &odd[1]), &odd[2])
+        );
+
+        assert_eq!(letters_root(&LETTERS_SECRET, "a"), odd[0]);
+        assert_eq!(letters_root(&LETTERS_SECRET, ""), [0u8; 32]);
+    }
+
+    #[test]
+    fn hash_pair_ignores_order() {
+        let (a, b) = ([1u8; 32], [2u8; 32]);
+        assert_eq!(hash_pair(&a, &b), hash_pair(&b, &a));
+    }
+
+    #[test]
+    fn verifies_every_position() {
+        for word in ["cat", "bird", "ice cream", "がっこう"] {
+            let root = letters_root(&LETTERS_SECRET, word);
+            for (i, leaf) in leaves(word).into_iter().enumerate() {
+                assert!(verify_letter_proof(&root, leaf, &proof(word, i)), "{} at {}", word, i);
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_wrong_letter() {
+        let root = letters_root(&LETTERS_SECRET, "bird");
+        let leaf = letter_leaf(&letter_nonce(&LETTERS_SECRET, 1), 1, "o");
+        assert!(!verify_letter_proof(&root, leaf, &proof("bird", 1)));
+    }
+
+    #[test]
+    fn rejects_wrong_position() {
+        // The right letter and nonce, claimed for the next position
+        let root = letters_root(&LETTERS_SECRET, "bird");
+        let leaf = letter_leaf(&letter_nonce(&LETTERS_SECRET, 1), 2, "i");
+        assert!(!verify_letter_proof(&root, leaf, &proof("bird", 1)));
+
+        // A valid leaf with another position's proof
+        let leaves = leaves("bird");
+        assert!(!verify_letter_proof(&root, leaves[2], &proof("bird", 0)));
+    }
+
+    #[test]
+    fn rejects_truncated_proof() {
+        for word in ["bird", "ice cream"] {
+            let root = letters_root(&LETTERS_SECRET, word);
+            for (i, leaf) in leaves(word).into_iter().enumerate() {
+                let proof = proof(word, i);
+                assert!(!verify_letter_proof(&root, leaf, &proof[..proof.len() - 1]), "{} at {}", word, i);
+                assert!(!verify_letter_proof(&root, leaf, &[]));
+            }
+        }
+    }
+
+    // Every spelling within edit distance 1, as a host builds it
+    fn edit_variants(word: &str) -> Vec<String> {
+        let chars: Vec<char> = word.chars().collect();
+        let alphabet = 'a'..='z';
+        let mut variants = vec![];
+        for i in 0..=chars.len() {
+            for letter in alphabet.clone() {
+                let mut inserted = chars.clone();
+                inserted.insert(i, letter);
+                variants.push(inserted.into_iter().collect());
+            }
+            if i == chars.len() {
+                break;
+            }
+
+            let mut deleted = chars.clone();
+            deleted.remove(i);
+            variants.push(deleted.into_iter().collect());
+
+            for letter in alphabet.clone().filter(|letter| *letter != chars[i]) {
+                let mut substituted = chars.clone();
+                substituted[i] = letter;
+                variants.push(substituted.into_iter().collect());
+            }
+
+            if i + 1 < chars.len() && chars[i] != chars[i + 1] {
+                let mut swapped = chars.clone();
+                swapped.swap(i, i + 1);
+                variants.push(swapped.into_iter().collect());
+            }
+        }
+        variants
+    }
+
+    #[test]
+    fn close_filter_finds_every_variant() {
+        let round_seed = [3u8; 32];
+        // As long as a word can be, so the filter is as full as it gets
+        let word = "the quick brown fox jumped overs";
+        assert_eq!(word.chars().count(), MAX_WORD_LEN);
+
+        let variants = edit_variants(word);
+        let mut filter = vec![0u8; CLOSE_FILTER_BYTES];
+        for variant in variants.iter() {
+            close_filter_insert(&mut filter, &round_seed, variant);
+        }
+
+        for variant in variants.iter() {
+            assert!(close_filter_contains(&filter, &round_seed, variant), "{}", variant);
+        }
+
+        // Unrelated words mostly miss, the filter is sized for about 1%
+        let hits = (0..2000)
+            .filter(|i| close_filter_contains(&filter, &round_seed, &format!("unrelated {}", i)))
+            .count();
+        assert!(hits <= 40, "{} of 2000 unrelated words hit", hits);
+
+        // The same filter means nothing under another round's seed
+        assert!(!close_filter_contains(&filter, &[4u8; 32], &variants[0]));
+    }
+
+    #[test]
+    fn close_filter_needs_a_full_filter() {
+        let round_seed = [3u8; 32];
+        let mut filter = vec![0u8; CLOSE_FILTER_BYTES];
+        close_filter_insert(&mut filter, &round_seed, "cat");
+
+        assert!(close_filter_contains(&filter, &round_seed, "cat"));
+        assert!(!close_filter_contains(&filter[..CLOSE_FILTER_BYTES - 1], &round_seed, "cat"));
+        assert!(!close_filter_contains(&[], &round_seed, "cat"));
+    }
+
+    fn assert_invalid(input: &str) {
+        assert_eq!(
+            normalize_word(input).unwrap_err(),
+            error!(crate::error::ErrorCode::InvalidWord),
+            "{:?}",
+            input
+        );
+    }
+
+    #[test]
+    fn normalize_strips_latin_diacritics() {
+        assert_eq!(normalize_word("Café").unwrap(), "cafe");
+        assert_eq!(normalize_word("CRÈME BRÛLÉE").unwrap(), "creme brulee");
+        assert_eq!(normalize_word("Ångström").unwrap(), "angstrom");
+    }
+
+    #[test]
+    fn normalize_collapses_whitespace_and_strips_punctuation() {
+        assert_eq!(normalize_word("  ice \t cream\n").unwrap(), "ice cream");
+        assert_eq!(normalize_word("don't!").unwrap(), "dont");
+        assert_eq!(normalize_word("Mr. Potato-Head?").unwrap(), "mr potato-head");
+    }
+
+    #[test]
+    fn normalize_keeps_hyphens() {
+        assert_eq!(normalize_word("T-Rex").unwrap(), "t-rex");
+        assert_ne!(normalize_word("t-rex").unwrap(), normalize_word("trex").unwrap());
+    }
+
+    #[test]
+    fn normalize_keeps_non_latin_combining_marks() {
+        // Voiced kana decompose into a base kana and a combining mark
+        assert_eq!(normalize_word("がっこう").unwrap(), "がっこう");
+        assert_ne!(normalize_word("がっこう").unwrap(), normalize_word("かっこう").unwrap());
+        // Thai vowel and tone marks, Devanagari virama
+        assert_eq!(normalize_word("ตู้").unwrap(), "ตู้");
+        assert_ne!(normalize_word("ตู้").unwrap(), normalize_word("ต").unwrap());
+        assert_eq!(normalize_word("क्या").unwrap(), "क्या");
+    }
+
+    #[test]
+    fn normalize_rejects_empty_words() {
+        assert_invalid("");
+        assert_invalid("   ");
+        assert_invalid("?!.");
+    }
+
+    #[test]
+    fn normalize_rejects_long_words() {
+        let longest = "a".repeat(MAX_WORD_LEN);
+        assert_eq!(normalize_word(&longest).unwrap(), longest);
+        assert_invalid(&"a".repeat(MAX_WORD_LEN + 1));
+
+        // Counted in characters, not bytes
+        let kana = "あ".repeat(MAX_WORD_LEN);
+        assert_eq!(normalize_word(&kana).unwrap(), kana);
+    }
+
+    #[test]
+    fn normalize_rejects_long_raw_input() {
+        // Normalizes to a single letter but is over the raw input limit
+        let padded = format!("a{}", " ".repeat(MAX_GUESS_BYTES));
+        assert_invalid(&padded);
+        assert_eq!(normalize_word(&padded[..MAX_GUESS_BYTES]).unwrap(), "a");
+    }
+}
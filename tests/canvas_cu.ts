@@ -0,0 +1,143 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { createHash, randomBytes } from "crypto";
+import { assert } from "chai";
+import { Karada } from "../target/types/karada";
+
+const sha256 = (...parts: Buffer[]) =>
+  createHash("sha256").update(Buffer.concat(parts)).digest();
+
+// Compute units of add_stroke as the canvas fills up. With the zero-copy ring
+// buffer the cost of one stroke should not depend on how many came before it.
+describe("canvas compute units", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.karada as Program<Karada>;
+  const connection = provider.connection;
+  const creator = provider.wallet;
+
+  const pda = (...seeds: Buffer[]) =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const airdrop = async (to: PublicKey) => {
+    const sig = await connection.requestAirdrop(to, 2 * LAMPORTS_PER_SOL);
+    await connection.confirmTransaction(sig, "confirmed");
+  };
+
+  const unitsConsumed = async (sig: string) => {
+    const tx = await connection.getTransaction(sig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    return tx.meta.computeUnitsConsumed;
+  };
+
+  it("measures add_stroke at 10, 100 and 500 strokes", async () => {
+    const gameCode = Array.from(randomBytes(6));
+    const gameConfig = pda(Buffer.from("game_config"), Buffer.from(gameCode));
+    const game = pda(Buffer.from("game"), gameConfig.toBuffer());
+
+    await program.methods
+      .initializeGame(gameCode, new BN(LAMPORTS_PER_SOL / 100), 2, null, { ring: {} })
+      .accountsPartial({ creator: creator.publicKey, gameConfig, wordBank: null })
+      .rpc();
+
+    // Two players commit to their entropy at join
+    const players = [Keypair.generate(), Keypair.generate()];
+    const entropies = players.map(() => randomBytes(32));
+    for (const [i, player] of players.entries()) {
+      await airdrop(player.publicKey);
+      const commitment = sha256(Buffer.from("commit"), player.publicKey.toBuffer(), entropies[i]);
+      await program.methods
+        .joinGame(Array.from(commitment))
+        .accountsPartial({ player: player.publicKey, gameConfig })
+        .signers([player])
+        .rpc();
+    }
+
+    const playerStates = players.map((player) =>
+      pda(Buffer.from("player_state"), gameConfig.toBuffer(), player.publicKey.toBuffer())
+    );
+
+    // A single candidate is chosen as soon as the game is seeded
+    const salt = randomBytes(32);
+    await program.methods
+      .startGame([
+        {
+          word: {
+            commitment: Array.from(sha256(salt, Buffer.from("cat"))),
+            shape: "___",
+            lettersRoot: new Array(32).fill(0),
+            alternates: [],
+          },
+          difficulty: 1,
+          bankIndex: null,
+        },
+      ])
+      .accountsPartial({ creator: creator.publicKey, gameConfig })
+      .remainingAccounts(
+        playerStates.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }))
+      )
+      .rpc();
+
+    for (const [i, player] of players.entries()) {
+      await program.methods
+        .revealEntropy(Array.from(entropies[i]))
+        .accountsPartial({ player: player.publicKey, gameConfig, game, wordBank: null })
+        .signers([player])
+        .rpc();
+    }
+
+    const { players: drawOrder } = await program.account.game.fetch(game);
+    const drawer = players.find((player) => player.publicKey.equals(drawOrder[0]));
+    // Round 0, page 0: ring canvases never move to another page
+    const canvas = pda(Buffer.from("canvas"), game.toBuffer(), Buffer.from([0]), Buffer.from([0]));
+
+    // A single point stroke takes 13 bytes of instruction data, so 40 of them
+    // stay well inside the 1232 byte transaction limit with signature and accounts
+    const FILL_BATCH = 40;
+
+    let strokeCount = 0;
+    const fillTo = async (target: number) => {
+      while (strokeCount < target) {
+        const batch = Math.min(FILL_BATCH, target - strokeCount);
+        const strokes = Array.from({ length: batch }, (_, k) => ({
+          points: [(strokeCount + k) % 800, (strokeCount + k) % 600],
+          color: 0x000000,
+          width: 2,
+        }));
+        await program.methods
+          .addStrokes(strokes)
+          .accountsPartial({ drawer: drawer.publicKey, gameConfig, game, canvas, nextCanvas: null })
+          .signers([drawer])
+          .rpc();
+        strokeCount += batch;
+      }
+    };
+
+    // A 25 point stroke, the largest add_stroke accepts
+    const measureStroke = async () => {
+      const points = Array.from({ length: 25 }, (_, k) => [100 + 4 * k, 200 + 3 * k]).flat();
+      const sig = await program.methods
+        .addStroke(points, 0xff0000, 4)
+        .accountsPartial({ drawer: drawer.publicKey, gameConfig, game, canvas, nextCanvas: null })
+        .signers([drawer])
+        .rpc({ commitment: "confirmed" });
+      strokeCount += 1;
+      return unitsConsumed(sig);
+    };
+
+    const results: Record<number, number> = {};
+    for (const target of [10, 100, 500]) {
+      await fillTo(target - 1);
+      results[target] = await measureStroke();
+    }
+
+    console.table(
+      Object.entries(results).map(([strokes, units]) => ({ strokes: Number(strokes), units }))
+    );
+    assert.isAtMost(results[500], results[10] * 1.1);
+  });
+});